@@ -1,113 +1,319 @@
-use std::collections::HashMap;
+use std::{future::Future, time::Instant};
 
-use riven::consts::{PlatformRoute, RegionalRoute};
+use futures::{stream, StreamExt};
+use riven::consts::{PlatformRoute, Queue, QueueType};
 use riven::models::champion_mastery_v4::ChampionMastery;
 use riven::models::league_v4::LeagueEntry;
-use riven::models::match_v5::Match;
-use riven::models::summoner_v4::Summoner;
-use riven::{RiotApi, RiotApiError};
+use riven::models::match_v5::{Match, MatchTimeline};
+use riven::RiotApiError;
+
+use crate::{error::WatcherError, logs, utils::LogKind};
+
+use super::{
+    cache::Kind,
+    client::WatcherClient,
+    player::{Player, RiotId},
+    routing::{account_route, match_route},
+    spectator::{active_game, LiveGame},
+    timeline::GameTimeline,
+};
+
+/// one riot request, how long it took and how it failed go to the log
+pub async fn timed<T>(
+    what: String,
+    request: impl Future<Output = Result<T, RiotApiError>>,
+) -> Result<T, RiotApiError> {
+    let start = Instant::now();
+    let res = request.await;
+    let ms = start.elapsed().as_millis();
+    match &res {
+        Ok(_) => logs::record(LogKind::Debug, format!("{} {}ms", what, ms)),
+        Err(e) => logs::record(LogKind::Error, format!("{} failed after {}ms: {}", what, ms, e)),
+    };
+    res
+}
 
 pub async fn get_rank(
-    api_key: &str,
+    client: &WatcherClient,
     route: PlatformRoute,
     id: &str,
-) -> Result<Vec<LeagueEntry>, RiotApiError> {
-    RiotApi::new(api_key)
-        .league_v4()
-        .get_league_entries_for_summoner(route, id)
-        .await
+) -> Result<Vec<LeagueEntry>, WatcherError> {
+    let key = format!("{}-{}", route, id);
+    if let Some(rank) = client.cache.get(Kind::Rank, &key) {
+        return Ok(rank);
+    }
+    let rank = timed(
+        format!("league-v4 entries {}", key),
+        client.riot.league_v4().get_league_entries_for_summoner(route, id),
+    )
+    .await?;
+    client.cache.put(Kind::Rank, &key, &rank);
+    Ok(rank)
 }
 
+/// `name` is either a Riot ID (`Name#TAG`) resolved through account-v1
+/// or a plain summoner name for platforms that still support it
 pub async fn get_summoner(
-    api_key: &str,
+    client: &WatcherClient,
     route: PlatformRoute,
     name: &str,
-) -> Result<Option<Summoner>, RiotApiError> {
-    let res = RiotApi::new(api_key)
-        .summoner_v4()
-        .get_by_summoner_name(route, name)
+) -> Result<Option<Player>, WatcherError> {
+    let key = format!("{}-{}", route, name);
+    if let Some(player) = client.cache.get::<Player>(Kind::Summoner, &key) {
+        return Ok(Some(player));
+    }
+    let player = fetch_summoner(client, route, name).await?;
+    if let Some(player) = &player {
+        client.cache.put(Kind::Summoner, &key, player);
+    }
+    Ok(player)
+}
+
+async fn fetch_summoner(
+    client: &WatcherClient,
+    route: PlatformRoute,
+    name: &str,
+) -> Result<Option<Player>, WatcherError> {
+    if let Some(riot_id) = RiotId::parse(name) {
+        let res = timed(
+            format!("account-v1 by riot id {}", riot_id),
+            client
+                .riot
+                .account_v1()
+                .get_by_riot_id(account_route(route), &riot_id.game_name, &riot_id.tag_line),
+        )
         .await;
-    match res {
-        Ok(op) => match op {
-            None => return Ok(None),
-            Some(s) => return Ok(Some(s)),
-        },
-        Err(e) => return Err(e),
+        let account = match res {
+            Ok(Some(a)) => a,
+            Ok(None) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let summoner = timed(
+            format!("summoner-v4 by puuid {}", account.puuid),
+            client.riot.summoner_v4().get_by_puuid(route, &account.puuid),
+        )
+        .await?;
+        return Ok(Some(Player {
+            summoner,
+            riot_id: RiotId::from_account(&account).or(Some(riot_id)),
+        }));
     }
+
+    let res = timed(
+        format!("summoner-v4 by name {}", name),
+        client.riot.summoner_v4().get_by_summoner_name(route, name),
+    )
+    .await;
+    let summoner = match res {
+        Ok(Some(s)) => s,
+        Ok(None) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let riot_id = timed(
+        format!("account-v1 by puuid {}", summoner.puuid),
+        client.riot.account_v1().get_by_puuid(account_route(route), &summoner.puuid),
+    )
+    .await
+    .ok()
+        .and_then(|a| RiotId::from_account(&a));
+    Ok(Some(Player { summoner, riot_id }))
 }
 
 pub async fn get_masteries(
-    api_key: &str,
+    client: &WatcherClient,
     route: PlatformRoute,
     id: &str,
     top: usize,
-) -> Result<Vec<ChampionMastery>, RiotApiError> {
-    let res = RiotApi::new(api_key)
-        .champion_mastery_v4()
-        .get_top_champion_masteries(route, id, Some(top as i32))
-        .await;
-    match res {
-        Ok(i) => {
-            return Ok(i);
+) -> Result<Vec<ChampionMastery>, WatcherError> {
+    let key = format!("{}-{}-{}", route, id, top);
+    if let Some(masteries) = client.cache.get(Kind::Mastery, &key) {
+        return Ok(masteries);
+    }
+    let masteries = timed(
+        format!("champion-mastery-v4 top {}", key),
+        client
+            .riot
+            .champion_mastery_v4()
+            .get_top_champion_masteries(route, id, Some(top as i32)),
+    )
+    .await?;
+    client.cache.put(Kind::Mastery, &key, &masteries);
+    Ok(masteries)
+}
+
+/// match-v5 returns at most 100 ids per request
+pub const MAX_IDS_PER_REQUEST: usize = 100;
+
+/// which part of the match history to fetch, times are unix seconds
+#[derive(Debug, Clone)]
+pub struct MatchQuery {
+    pub start: usize,
+    pub count: usize,
+    pub queue: Option<Queue>,
+    pub match_type: Option<String>,
+    pub start_time: Option<i64>,
+    pub end_time: Option<i64>,
+}
+
+impl Default for MatchQuery {
+    fn default() -> Self {
+        MatchQuery {
+            start: 0,
+            count: 20,
+            queue: None,
+            match_type: None,
+            start_time: None,
+            end_time: None,
         }
-        Err(e) => return Err(e),
     }
 }
 
-pub async fn get_games(
-    api_key: &str,
+impl MatchQuery {
+    /// same filters, following page
+    pub fn next_page(&self) -> MatchQuery {
+        MatchQuery {
+            start: self.start + self.count,
+            ..self.clone()
+        }
+    }
+}
+
+/// games that downloaded plus (match id, reason) for every one that didn't,
+/// `more` is false once the history ran out
+#[derive(Debug, Default)]
+pub struct MatchList {
+    pub matches: Vec<Match>,
+    pub failed: Vec<(String, WatcherError)>,
+    pub more: bool,
+}
+
+pub async fn get_match_ids(
+    client: &WatcherClient,
     route: PlatformRoute,
     puuid: &str,
-) -> Result<Vec<Match>, RiotApiError> {
-    let mut remap = HashMap::<Vec<PlatformRoute>, RegionalRoute>::new();
-    remap.insert(
-        [PlatformRoute::NA1, PlatformRoute::BR1].to_vec(),
-        RegionalRoute::AMERICAS,
-    );
-    remap.insert(
-        [PlatformRoute::EUN1, PlatformRoute::EUW1].to_vec(),
-        RegionalRoute::EUROPE,
-    );
-    remap.insert(
-        [PlatformRoute::KR, PlatformRoute::JP1, PlatformRoute::TW2].to_vec(),
-        RegionalRoute::ASIA,
-    );
-    let riot = RiotApi::new(api_key);
-
-    let res = riot
-        .match_v5()
-        .get_match_ids_by_puuid(
-            *remap.get(&vec![route]).unwrap_or(&RegionalRoute::EUROPE),
-            puuid,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
+    query: &MatchQuery,
+) -> Result<Vec<String>, WatcherError> {
+    let mut ids: Vec<String> = vec![];
+    while ids.len() < query.count {
+        let count = (query.count - ids.len()).min(MAX_IDS_PER_REQUEST);
+        let page = timed(
+            format!("match-v5 ids {} {}+{}", puuid, query.start + ids.len(), count),
+            client.riot.match_v5().get_match_ids_by_puuid(
+                match_route(route),
+                puuid,
+                Some(count as i32),
+                query.end_time,
+                query.queue,
+                query.start_time,
+                Some((query.start + ids.len()) as i32),
+                query.match_type.as_deref(),
+            ),
         )
+        .await?;
+        let last = page.len() < count;
+        ids.extend(page);
+        if last {
+            break;
+        }
+    }
+    Ok(ids)
+}
+
+pub async fn get_games(
+    client: &WatcherClient,
+    route: PlatformRoute,
+    puuid: &str,
+    query: &MatchQuery,
+) -> Result<MatchList, WatcherError> {
+    let ids = get_match_ids(client, route, puuid, query).await?;
+    let more = ids.len() == query.count;
+
+    let results = stream::iter(ids)
+        .map(|id| async move {
+            let res = get_match(client, route, &id).await;
+            (id, res)
+        })
+        .buffered(client.match_concurrency)
+        .collect::<Vec<_>>()
         .await;
-    let ids = match res {
-        Ok(i) => i,
-        Err(e) => return Err(e),
-    };
 
-    let mut matches: Vec<Match> = vec![];
-    for id in ids.iter() {
-        let res = riot
-            .match_v5()
-            .get_match(
-                *remap.get(&vec![route]).unwrap_or(&RegionalRoute::EUROPE),
-                &id,
-            )
-            .await;
+    let mut games = MatchList {
+        more,
+        ..MatchList::default()
+    };
+    for (id, res) in results {
         match res {
-            Ok(i) => match i {
-                Some(m) => matches.push(m),
-                None => {}
-            },
-            Err(e) => return Err(e),
+            Ok(m) => games.matches.push(m),
+            Err(e) => games.failed.push((id, e)),
         }
     }
-    Ok(matches)
+    Ok(games)
+}
+
+/// finished matches never change, so a cached one is always used
+pub async fn get_match(
+    client: &WatcherClient,
+    route: PlatformRoute,
+    id: &str,
+) -> Result<Match, WatcherError> {
+    if let Some(m) = client.cache.get::<Match>(Kind::Match, id) {
+        return Ok(m);
+    }
+    let m = timed(
+        format!("match-v5 match {}", id),
+        client.riot.match_v5().get_match(match_route(route), id),
+    )
+    .await?
+        .ok_or_else(|| WatcherError::NotFound(id.to_string()))?;
+    client.cache.put(Kind::Match, id, &m);
+    Ok(m)
+}
+
+/// cached like matches, a finished game's timeline never changes
+pub async fn get_timeline(
+    client: &WatcherClient,
+    route: PlatformRoute,
+    game: &Match,
+) -> Result<GameTimeline, WatcherError> {
+    let id = &game.metadata.match_id;
+    let timeline = match client.cache.get::<MatchTimeline>(Kind::Timeline, id) {
+        Some(t) => t,
+        None => {
+            let t = timed(
+                format!("match-v5 timeline {}", id),
+                client.riot.match_v5().get_timeline(match_route(route), id),
+            )
+            .await?
+                .ok_or_else(|| WatcherError::NotFound(format!("timeline of {}", id)))?;
+            client.cache.put(Kind::Timeline, id, &t);
+            t
+        }
+    };
+    Ok(GameTimeline::new(&timeline, game))
+}
+
+/// None when the player isn't in a game, ranks that fail to load are left empty
+pub async fn get_live_game(
+    client: &WatcherClient,
+    route: PlatformRoute,
+    puuid: &str,
+) -> Result<Option<LiveGame>, WatcherError> {
+    let game = match active_game(client, route, puuid).await? {
+        Some(game) => game,
+        None => return Ok(None),
+    };
+    let ids = game.participants.iter().map(|p| p.summoner_id.clone()).collect::<Vec<_>>();
+    let ranks = stream::iter(ids)
+        .map(|id| async move {
+            let entries = get_rank(client, route, &id?).await.ok()?;
+            entries
+                .iter()
+                .find(|e| e.queue_type == QueueType::RANKED_SOLO_5x5)
+                .or_else(|| entries.first())
+                .cloned()
+        })
+        .buffered(client.batch_concurrency)
+        .collect::<Vec<_>>()
+        .await;
+    Ok(Some(LiveGame { game, ranks }))
 }
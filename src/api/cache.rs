@@ -0,0 +1,176 @@
+use std::{
+    fs,
+    path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, SystemTime},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheMode {
+    Normal,
+    /// skip reads but store fresh responses, `--refresh`
+    Refresh,
+    /// neither read nor write, `--no-cache`
+    Off,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Match,
+    Summoner,
+    Rank,
+    Mastery,
+    Timeline,
+}
+
+impl Kind {
+    pub const ALL: [Kind; 5] = [Kind::Match, Kind::Summoner, Kind::Rank, Kind::Mastery, Kind::Timeline];
+
+    fn dir(&self) -> &'static str {
+        match self {
+            Kind::Match => "matches",
+            Kind::Summoner => "summoners",
+            Kind::Rank => "ranks",
+            Kind::Mastery => "masteries",
+            Kind::Timeline => "timelines",
+        }
+    }
+}
+
+/// `[cache]` in config.toml, ttls are seconds, finished matches never expire
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    pub dir: Option<PathBuf>,
+    pub summoner_ttl: u64,
+    pub rank_ttl: u64,
+    pub mastery_ttl: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig {
+            dir: None,
+            summoner_ttl: 24 * 60 * 60,
+            rank_ttl: 10 * 60,
+            mastery_ttl: 60 * 60,
+        }
+    }
+}
+
+/// json files under the XDG cache dir, one per response
+pub struct Cache {
+    pub dir: Option<PathBuf>,
+    pub mode: CacheMode,
+    pub config: CacheConfig,
+}
+
+impl Cache {
+    pub fn new(config: &CacheConfig, mode: CacheMode) -> Cache {
+        let dir = match &config.dir {
+            Some(dir) => Some(crate::config::expand_home(dir)),
+            None => dirs::cache_dir().map(|d| d.join("watcher")),
+        };
+        Cache {
+            dir,
+            mode,
+            config: config.clone(),
+        }
+    }
+
+    fn ttl(&self, kind: Kind) -> Option<Duration> {
+        match kind {
+            Kind::Match | Kind::Timeline => None,
+            Kind::Summoner => Some(Duration::from_secs(self.config.summoner_ttl)),
+            Kind::Rank => Some(Duration::from_secs(self.config.rank_ttl)),
+            Kind::Mastery => Some(Duration::from_secs(self.config.mastery_ttl)),
+        }
+    }
+
+    fn path(&self, kind: Kind, key: &str) -> Option<PathBuf> {
+        let file = key
+            .to_lowercase()
+            .bytes()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>();
+        self.dir
+            .as_ref()
+            .map(|d| d.join(kind.dir()).join(format!("{}.json", file)))
+    }
+
+    pub fn get<T: DeserializeOwned>(&self, kind: Kind, key: &str) -> Option<T> {
+        if self.mode != CacheMode::Normal {
+            return None;
+        }
+        let path = self.path(kind, key)?;
+        if let Some(ttl) = self.ttl(kind) {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            let age = SystemTime::now().duration_since(modified).unwrap_or_default();
+            if age > ttl {
+                return None;
+            }
+        }
+        let text = fs::read_to_string(&path).ok()?;
+        match serde_json::from_str(&text) {
+            Ok(value) => Some(value),
+            // a truncated or outdated entry is a miss and goes away
+            Err(_) => {
+                let _ = fs::remove_file(path);
+                None
+            }
+        }
+    }
+
+    /// best effort, a cache that can't be written just means more requests,
+    /// written next to it and renamed so a killed process or a second writer never leaves half a file
+    pub fn put<T: Serialize>(&self, kind: Kind, key: &str, value: &T) {
+        if self.mode == CacheMode::Off {
+            return;
+        }
+        let path = match self.path(kind, key) {
+            Some(p) => p,
+            None => return,
+        };
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(text) = serde_json::to_string(value) {
+            static NEXT: AtomicU64 = AtomicU64::new(0);
+            let n = NEXT.fetch_add(1, Ordering::Relaxed);
+            let tmp = path.with_extension(format!("tmp{}-{}", std::process::id(), n));
+            if fs::write(&tmp, text).is_ok() && fs::rename(&tmp, &path).is_err() {
+                let _ = fs::remove_file(&tmp);
+            }
+        }
+    }
+
+    /// the next `get` goes to the api again
+    pub fn remove(&self, kind: Kind, key: &str) {
+        if let Some(path) = self.path(kind, key) {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    /// (kind, files, bytes) for every kind
+    pub fn size(&self) -> Vec<(Kind, usize, u64)> {
+        Kind::ALL
+            .iter()
+            .map(|kind| {
+                let dir = self.dir.as_ref().map(|d| d.join(kind.dir()));
+                let entries = dir
+                    .and_then(|d| fs::read_dir(d).ok())
+                    .map(|r| r.filter_map(|e| e.ok()?.metadata().ok()).collect::<Vec<_>>())
+                    .unwrap_or_default();
+                (*kind, entries.len(), entries.iter().map(|m| m.len()).sum())
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.dir())
+    }
+}
@@ -0,0 +1,33 @@
+use riven::{RiotApi, RiotApiConfig};
+
+use crate::config::Config;
+
+use super::{
+    cache::{Cache, CacheMode},
+    credentials::ApiKey,
+};
+
+/// one RiotApi per session so riven's rate limiter and connection pool
+/// are shared by every request
+pub struct WatcherClient {
+    pub riot: RiotApi,
+    pub api_key: ApiKey,
+    /// how many matches are downloaded at once
+    pub match_concurrency: usize,
+    /// how many players of a batch are looked up at once
+    pub batch_concurrency: usize,
+    pub cache: Cache,
+}
+
+impl WatcherClient {
+    pub fn new(api_key: ApiKey, config: &Config, cache_mode: CacheMode) -> WatcherClient {
+        let riot = RiotApi::new(RiotApiConfig::with_key(&api_key.key));
+        WatcherClient {
+            riot,
+            api_key,
+            match_concurrency: config.match_concurrency.max(1),
+            batch_concurrency: config.batch_concurrency.max(1),
+            cache: Cache::new(&config.cache, cache_mode),
+        }
+    }
+}
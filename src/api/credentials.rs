@@ -0,0 +1,99 @@
+use std::{env, fmt::Display, fs, path::PathBuf};
+
+use crate::{
+    config::{config_path, Config},
+    error::WatcherError,
+};
+
+pub const KEY_ENV: &str = "RGAPI_KEY";
+pub const KEY_FLAG: &str = "--api-key";
+
+/// where the key was found, checked in this order:
+/// `--api-key` flag, `RGAPI_KEY` env var, `api_key` in config.toml, key file
+#[derive(Debug, Clone)]
+pub enum KeySource {
+    Flag,
+    Env,
+    Config(PathBuf),
+    KeyFile(PathBuf),
+}
+
+impl Display for KeySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeySource::Flag => write!(f, "{} flag", KEY_FLAG),
+            KeySource::Env => write!(f, "{} env var", KEY_ENV),
+            KeySource::Config(p) => write!(f, "config file {}", p.display()),
+            KeySource::KeyFile(p) => write!(f, "key file {}", p.display()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiKey {
+    pub key: String,
+    pub source: KeySource,
+}
+
+impl ApiKey {
+    pub fn resolve(flag: Option<String>, config: &Config) -> Result<ApiKey, String> {
+        if let Some(key) = non_empty(flag) {
+            return Ok(ApiKey {
+                key,
+                source: KeySource::Flag,
+            });
+        }
+        if let Some(key) = non_empty(env::var(KEY_ENV).ok()) {
+            return Ok(ApiKey {
+                key,
+                source: KeySource::Env,
+            });
+        }
+        if let Some(key) = non_empty(config.api_key.clone()) {
+            return Ok(ApiKey {
+                key,
+                source: KeySource::Config(config_path().unwrap_or_default()),
+            });
+        }
+        if let Some(path) = config.key_file() {
+            if path.exists() {
+                let key = fs::read_to_string(&path)
+                    .map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+                if let Some(key) = non_empty(Some(key)) {
+                    return Ok(ApiKey {
+                        key,
+                        source: KeySource::KeyFile(path),
+                    });
+                }
+            }
+        }
+        Err(format!(
+            "no Riot API key found, pass {} <key>, export {}, set api_key in {} or write it to {}",
+            KEY_FLAG,
+            KEY_ENV,
+            config_path().unwrap_or_default().display(),
+            config.key_file().unwrap_or_default().display(),
+        ))
+    }
+
+    /// Riot answers 403 once a development key passes its 24h lifetime
+    pub fn expired_hint(&self, err: &WatcherError) -> Option<String> {
+        match err {
+            WatcherError::Forbidden => Some(format!(
+                "API key from {} was rejected (403), development keys expire after 24h, regenerate it at https://developer.riotgames.com",
+                self.source
+            )),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ApiKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "using API key from {}", self.source)
+    }
+}
+
+fn non_empty(key: Option<String>) -> Option<String> {
+    key.map(|k| k.trim().to_string()).filter(|k| !k.is_empty())
+}
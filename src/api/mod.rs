@@ -1 +1,8 @@
 pub mod api;
+pub mod cache;
+pub mod client;
+pub mod credentials;
+pub mod player;
+pub mod routing;
+pub mod spectator;
+pub mod timeline;
@@ -0,0 +1,58 @@
+use std::fmt::Display;
+
+use riven::models::{account_v1::Account, summoner_v4::Summoner};
+use serde::{Deserialize, Serialize};
+
+/// `gameName#tagLine`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RiotId {
+    pub game_name: String,
+    pub tag_line: String,
+}
+
+impl RiotId {
+    pub fn parse(input: &str) -> Option<RiotId> {
+        let (game_name, tag_line) = input.trim().split_once('#')?;
+        let (game_name, tag_line) = (game_name.trim(), tag_line.trim());
+        if game_name.is_empty() || tag_line.is_empty() {
+            return None;
+        }
+        Some(RiotId {
+            game_name: game_name.into(),
+            tag_line: tag_line.into(),
+        })
+    }
+
+    pub fn from_account(account: &Account) -> Option<RiotId> {
+        match (&account.game_name, &account.tag_line) {
+            (Some(game_name), Some(tag_line)) => Some(RiotId {
+                game_name: game_name.clone(),
+                tag_line: tag_line.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl Display for RiotId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}#{}", self.game_name, self.tag_line)
+    }
+}
+
+/// summoner together with the Riot ID of its account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Player {
+    pub summoner: Summoner,
+    pub riot_id: Option<RiotId>,
+}
+
+impl Player {
+    /// Riot ID when known, old summoner name otherwise
+    pub fn name(&self) -> String {
+        match &self.riot_id {
+            Some(id) => id.to_string(),
+            None => self.summoner.name.clone(),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use riven::consts::{PlatformRoute, RegionalRoute};
+
+/// match-v5 cluster a platform's games are stored on
+pub fn match_route(route: PlatformRoute) -> RegionalRoute {
+    match route {
+        PlatformRoute::NA1
+        | PlatformRoute::BR1
+        | PlatformRoute::LA1
+        | PlatformRoute::LA2
+        | PlatformRoute::PBE1 => RegionalRoute::AMERICAS,
+        PlatformRoute::EUW1 | PlatformRoute::EUN1 | PlatformRoute::TR1 | PlatformRoute::RU => {
+            RegionalRoute::EUROPE
+        }
+        PlatformRoute::KR | PlatformRoute::JP1 => RegionalRoute::ASIA,
+        PlatformRoute::OC1
+        | PlatformRoute::PH2
+        | PlatformRoute::SG2
+        | PlatformRoute::TH2
+        | PlatformRoute::TW2
+        | PlatformRoute::VN2 => RegionalRoute::SEA,
+        route => route.to_regional(),
+    }
+}
+
+/// account-v1 has no SEA cluster, every account can be queried on any of
+/// AMERICAS, ASIA and EUROPE so SEA platforms use the closest one
+pub fn account_route(route: PlatformRoute) -> RegionalRoute {
+    match match_route(route) {
+        RegionalRoute::SEA => RegionalRoute::ASIA,
+        regional => regional,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use riven::consts::{PlatformRoute, RegionalRoute};
+
+    use super::{account_route, match_route};
+    use crate::utils::{routes, ROUTE_NAMES};
+
+    const EXPECTED: [(&str, PlatformRoute, RegionalRoute, RegionalRoute); 16] = [
+        ("kr", PlatformRoute::KR, RegionalRoute::ASIA, RegionalRoute::ASIA),
+        ("ru", PlatformRoute::RU, RegionalRoute::EUROPE, RegionalRoute::EUROPE),
+        ("br", PlatformRoute::BR1, RegionalRoute::AMERICAS, RegionalRoute::AMERICAS),
+        ("jp", PlatformRoute::JP1, RegionalRoute::ASIA, RegionalRoute::ASIA),
+        ("la1", PlatformRoute::LA1, RegionalRoute::AMERICAS, RegionalRoute::AMERICAS),
+        ("la2", PlatformRoute::LA2, RegionalRoute::AMERICAS, RegionalRoute::AMERICAS),
+        ("na", PlatformRoute::NA1, RegionalRoute::AMERICAS, RegionalRoute::AMERICAS),
+        ("oce", PlatformRoute::OC1, RegionalRoute::SEA, RegionalRoute::ASIA),
+        ("ph", PlatformRoute::PH2, RegionalRoute::SEA, RegionalRoute::ASIA),
+        ("sg", PlatformRoute::SG2, RegionalRoute::SEA, RegionalRoute::ASIA),
+        ("th", PlatformRoute::TH2, RegionalRoute::SEA, RegionalRoute::ASIA),
+        ("tr", PlatformRoute::TR1, RegionalRoute::EUROPE, RegionalRoute::EUROPE),
+        ("tw", PlatformRoute::TW2, RegionalRoute::SEA, RegionalRoute::ASIA),
+        ("vn", PlatformRoute::VN2, RegionalRoute::SEA, RegionalRoute::ASIA),
+        ("eune", PlatformRoute::EUN1, RegionalRoute::EUROPE, RegionalRoute::EUROPE),
+        ("euw", PlatformRoute::EUW1, RegionalRoute::EUROPE, RegionalRoute::EUROPE),
+    ];
+
+    #[test]
+    fn every_route_is_covered() {
+        let routes = routes();
+        assert_eq!(routes.len(), EXPECTED.len());
+        assert_eq!(ROUTE_NAMES.len(), EXPECTED.len());
+        for (name, route) in routes.iter() {
+            assert!(
+                EXPECTED.iter().any(|e| e.0 == name.as_str() && e.1 == *route),
+                "{} => {} has no expected mapping",
+                name,
+                route
+            );
+            assert!(ROUTE_NAMES.contains(&name.as_str()));
+        }
+    }
+
+    #[test]
+    fn match_routes() {
+        for (name, route, regional, _) in EXPECTED {
+            assert_eq!(match_route(route), regional, "match-v5 route for {}", name);
+        }
+    }
+
+    #[test]
+    fn account_routes() {
+        for (name, route, _, regional) in EXPECTED {
+            assert_eq!(account_route(route), regional, "account-v1 route for {}", name);
+        }
+    }
+
+    #[test]
+    fn match_routes_agree_with_riven() {
+        for (_, route) in routes() {
+            assert_eq!(match_route(route), route.to_regional());
+        }
+    }
+
+    #[test]
+    fn no_sea_for_account() {
+        for (_, route) in routes() {
+            assert_ne!(account_route(route), RegionalRoute::SEA);
+        }
+    }
+}
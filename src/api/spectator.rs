@@ -0,0 +1,96 @@
+//! spectator-v5, riven 2.19 only wraps v4 which looks players up by summoner id
+
+use riven::{
+    consts::{Champion, PlatformRoute, Queue, Team},
+    models::league_v4::LeagueEntry,
+    reqwest::Method,
+    RiotApiError,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{api::timed, client::WatcherClient};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrentGame {
+    pub game_id: i64,
+    pub game_type: String,
+    /// unix millis, 0 while the game is loading
+    pub game_start_time: i64,
+    pub map_id: i64,
+    /// seconds
+    pub game_length: i64,
+    pub platform_id: String,
+    pub game_mode: String,
+    pub banned_champions: Vec<BannedChampion>,
+    pub game_queue_config_id: Option<Queue>,
+    pub participants: Vec<CurrentParticipant>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BannedChampion {
+    pub pick_turn: i32,
+    pub champion_id: Champion,
+    pub team_id: Team,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrentParticipant {
+    pub champion_id: Champion,
+    pub perks: Option<Perks>,
+    pub bot: bool,
+    pub team_id: Team,
+    pub puuid: Option<String>,
+    pub summoner_id: Option<String>,
+    pub riot_id: Option<String>,
+    pub spell1_id: i64,
+    pub spell2_id: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Perks {
+    pub perk_ids: Vec<i64>,
+    pub perk_style: i64,
+    pub perk_sub_style: i64,
+}
+
+impl CurrentGame {
+    /// seconds since the game started
+    pub fn elapsed(&self) -> i64 {
+        if self.game_start_time <= 0 {
+            return self.game_length.max(0);
+        }
+        (chrono::Utc::now().timestamp_millis() - self.game_start_time).max(0) / 1000
+    }
+}
+
+/// None when the player isn't in a game
+pub async fn active_game(
+    client: &WatcherClient,
+    route: PlatformRoute,
+    puuid: &str,
+) -> Result<Option<CurrentGame>, RiotApiError> {
+    let platform: &'static str = route.into();
+    let request = client.riot.request(
+        Method::GET,
+        platform,
+        &format!("/lol/spectator/v5/active-games/by-summoner/{}", puuid),
+    );
+    timed(
+        format!("spectator-v5 active game {}", puuid),
+        client
+            .riot
+            .execute_opt::<CurrentGame>("spectator-v5.getCurrentGameInfoByPuuid", platform, request),
+    )
+    .await
+}
+
+/// a game in progress with the solo queue rank of every participant, same order
+#[derive(Debug, Clone)]
+pub struct LiveGame {
+    pub game: CurrentGame,
+    pub ranks: Vec<Option<LeagueEntry>>,
+}
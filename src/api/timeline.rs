@@ -0,0 +1,297 @@
+//! match-v5 timeline boiled down to what the timeline view draws
+
+use riven::{
+    consts::Team,
+    models::match_v5::{Match, MatchTimeline, MatchTimelineInfoFrame, MatchTimelineInfoFrameParticipantFrame},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    Kill,
+    Dragon,
+    Baron,
+    Herald,
+    /// voidgrubs, atakhan and whatever Riot adds next
+    Monster,
+    Tower,
+    Inhibitor,
+}
+
+#[derive(Debug, Clone)]
+pub struct TimelineEvent {
+    /// millis since the start
+    pub timestamp: i32,
+    /// team that got it
+    pub team: Team,
+    pub kind: EventKind,
+    pub text: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct GameTimeline {
+    pub match_id: String,
+    /// (minute, blue - red) once per frame
+    pub gold: Vec<(f64, f64)>,
+    pub xp: Vec<(f64, f64)>,
+    pub events: Vec<TimelineEvent>,
+}
+
+impl GameTimeline {
+    /// `game` is the same match, the timeline only knows participant ids
+    pub fn new(timeline: &MatchTimeline, game: &Match) -> GameTimeline {
+        let team_of = |id: i32| {
+            game.info
+                .participants
+                .iter()
+                .find(|p| p.participant_id == id)
+                .map(|p| p.team_id)
+                .unwrap_or(if id <= 5 { Team::BLUE } else { Team::RED })
+        };
+        // killer 0 is minions, towers or an execute
+        let name_of = |id: i32| match id {
+            0 => "minions".to_string(),
+            _ => game
+                .info
+                .participants
+                .iter()
+                .find(|p| p.participant_id == id)
+                .map(|p| p.champion_name.clone())
+                .unwrap_or_else(|| format!("#{}", id)),
+        };
+
+        let mut gold = vec![];
+        let mut xp = vec![];
+        let mut events = vec![];
+        for frame in &timeline.info.frames {
+            let minute = frame.timestamp as f64 / 60_000.0;
+            let (mut gold_diff, mut xp_diff) = (0, 0);
+            for p in participant_frames(frame) {
+                let sign = if team_of(p.participant_id) == Team::BLUE { 1 } else { -1 };
+                gold_diff += sign * p.total_gold;
+                xp_diff += sign * p.xp;
+            }
+            gold.push((minute, gold_diff as f64));
+            xp.push((minute, xp_diff as f64));
+
+            for e in &frame.events {
+                let event = match e.r#type.as_str() {
+                    "CHAMPION_KILL" => {
+                        let killer = e.killer_id.unwrap_or(0);
+                        let victim = e.victim_id.unwrap_or(0);
+                        let assists = e.assisting_participant_ids.as_ref().map(|a| a.len()).unwrap_or(0);
+                        let mut text = format!("{} killed {}", name_of(killer), name_of(victim));
+                        if assists > 0 {
+                            text += &format!(" +{}", assists);
+                        }
+                        TimelineEvent {
+                            timestamp: e.timestamp,
+                            team: if killer == 0 { e.killer_team_id.unwrap_or(Team::OTHER) } else { team_of(killer) },
+                            kind: EventKind::Kill,
+                            text,
+                        }
+                    }
+                    "ELITE_MONSTER_KILL" => {
+                        let monster = e.monster_type.clone().unwrap_or_default();
+                        let (kind, text) = match monster.as_str() {
+                            "DRAGON" => (
+                                EventKind::Dragon,
+                                e.monster_sub_type
+                                    .as_deref()
+                                    .map(readable)
+                                    .unwrap_or_else(|| "dragon".to_string()),
+                            ),
+                            "BARON_NASHOR" => (EventKind::Baron, "baron".to_string()),
+                            "RIFTHERALD" => (EventKind::Herald, "herald".to_string()),
+                            _ => (EventKind::Monster, readable(&monster)),
+                        };
+                        TimelineEvent {
+                            timestamp: e.timestamp,
+                            team: e.killer_team_id.unwrap_or_else(|| team_of(e.killer_id.unwrap_or(0))),
+                            kind,
+                            text,
+                        }
+                    }
+                    "BUILDING_KILL" => {
+                        let building = e.building_type.clone().unwrap_or_default();
+                        let lane = e.lane_type.as_deref().map(readable).unwrap_or_default();
+                        let (kind, text) = match building.as_str() {
+                            "INHIBITOR_BUILDING" => (EventKind::Inhibitor, format!("{} inhibitor", lane)),
+                            _ => (
+                                EventKind::Tower,
+                                format!("{} {}", lane, e.tower_type.as_deref().map(readable).unwrap_or_default()),
+                            ),
+                        };
+                        // team_id is the team that lost the building
+                        let team = match e.team_id {
+                            Some(Team::BLUE) => Team::RED,
+                            Some(Team::RED) => Team::BLUE,
+                            _ => team_of(e.killer_id.unwrap_or(0)),
+                        };
+                        TimelineEvent { timestamp: e.timestamp, team, kind, text }
+                    }
+                    _ => continue,
+                };
+                events.push(event);
+            }
+        }
+
+        GameTimeline {
+            match_id: game.metadata.match_id.clone(),
+            gold,
+            xp,
+            events,
+        }
+    }
+}
+
+fn participant_frames(frame: &MatchTimelineInfoFrame) -> [&MatchTimelineInfoFrameParticipantFrame; 10] {
+    let f = &frame.participant_frames;
+    [&f.x1, &f.x2, &f.x3, &f.x4, &f.x5, &f.x6, &f.x7, &f.x8, &f.x9, &f.x10]
+}
+
+/// FIRE_DRAGON -> fire dragon
+fn readable(s: &str) -> String {
+    s.to_lowercase().replace('_', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use riven::{consts::Team, models::match_v5::{Match, MatchTimeline}};
+    use serde_json::{json, Value};
+
+    use super::{EventKind, GameTimeline};
+
+    /// riven wants every stat, only gold and xp matter here
+    fn participant_frame(id: i32, gold: i32, xp: i32) -> Value {
+        let zeros = |names: &[&str]| names.iter().map(|n| (n.to_string(), json!(0))).collect::<serde_json::Map<_, _>>();
+        json!({
+            "championStats": zeros(&[
+                "abilityPower", "armor", "armorPen", "armorPenPercent", "attackDamage", "attackSpeed",
+                "bonusArmorPenPercent", "bonusMagicPenPercent", "ccReduction", "cooldownReduction", "health",
+                "healthMax", "healthRegen", "lifesteal", "magicPen", "magicPenPercent", "magicResist",
+                "movementSpeed", "power", "powerMax", "powerRegen", "spellVamp",
+            ]),
+            "damageStats": zeros(&[
+                "magicDamageDone", "magicDamageDoneToChampions", "magicDamageTaken", "physicalDamageDone",
+                "physicalDamageDoneToChampions", "physicalDamageTaken", "totalDamageDone",
+                "totalDamageDoneToChampions", "totalDamageTaken", "trueDamageDone", "trueDamageDoneToChampions",
+                "trueDamageTaken",
+            ]),
+            "currentGold": 0, "goldPerSecond": 0, "jungleMinionsKilled": 0, "level": 1, "minionsKilled": 0,
+            "participantId": id, "position": {"x": 0, "y": 0}, "timeEnemySpentControlled": 0,
+            "totalGold": gold, "xp": xp,
+        })
+    }
+
+    /// blue 6..=10 has `blue` gold and xp each, red 1..=5 `red`
+    fn frame(minute: i32, blue: i32, red: i32, events: Value) -> Value {
+        let frames = (1..=10)
+            .map(|id| {
+                let value = if id <= 5 { red } else { blue };
+                (id.to_string(), participant_frame(id, value, value))
+            })
+            .collect::<serde_json::Map<_, _>>();
+        json!({"timestamp": minute * 60_000, "participantFrames": frames, "events": events})
+    }
+
+    /// riven wants every field of a participant, only id, champion and team matter here
+    fn participant(id: i32, champion: &str, team: u16) -> Value {
+        const NUMBERS: &str = "assists baronKills bountyLevel champExperience champLevel championId championTransform
+            consumablesPurchased damageDealtToObjectives damageDealtToTurrets damageSelfMitigated deaths
+            detectorWardsPlaced doubleKills dragonKills goldEarned goldSpent inhibitorKills item0 item1 item2 item3
+            item4 item5 item6 itemsPurchased killingSprees kills largestCriticalStrike largestKillingSpree
+            largestMultiKill longestTimeSpentLiving magicDamageDealt magicDamageDealtToChampions magicDamageTaken
+            neutralMinionsKilled nexusKills objectivesStolen objectivesStolenAssists pentaKills physicalDamageDealt
+            physicalDamageDealtToChampions physicalDamageTaken profileIcon quadraKills sightWardsBoughtInGame
+            spell1Casts spell2Casts spell3Casts spell4Casts summoner1Casts summoner1Id summoner2Casts summoner2Id
+            summonerLevel timeCCingOthers timePlayed totalDamageDealt totalDamageDealtToChampions
+            totalDamageShieldedOnTeammates totalDamageTaken totalHeal totalHealsOnTeammates totalMinionsKilled
+            totalTimeCCDealt totalTimeSpentDead totalUnitsHealed tripleKills trueDamageDealt
+            trueDamageDealtToChampions trueDamageTaken turretKills unrealKills visionScore visionWardsBoughtInGame
+            wardsKilled wardsPlaced";
+        const FLAGS: &str = "firstBloodAssist firstBloodKill firstTowerAssist firstTowerKill gameEndedInEarlySurrender
+            gameEndedInSurrender teamEarlySurrendered win";
+        const TEXT: &str = "individualPosition lane puuid riotIdName riotIdTagline role summonerId summonerName
+            teamPosition";
+        let mut p = serde_json::Map::new();
+        for (names, value) in [(NUMBERS, json!(0)), (FLAGS, json!(false)), (TEXT, json!(""))] {
+            for name in names.split_whitespace() {
+                p.insert(name.to_string(), value.clone());
+            }
+        }
+        p.insert("perks".into(), json!({"statPerks": {"defense": 0, "flex": 0, "offense": 0}, "styles": []}));
+        p.insert("participantId".into(), json!(id));
+        p.insert("championName".into(), json!(champion));
+        p.insert("teamId".into(), json!(team));
+        Value::Object(p)
+    }
+
+    /// red is 1-5 and blue 6-10, the other way around from the fallback, so teams come from the lookup
+    fn game() -> Match {
+        let champions = ["Garen", "LeeSin", "Ahri", "Jinx", "Thresh", "Darius", "Viego", "Zed", "Caitlyn", "Lux"];
+        let participants = champions
+            .iter()
+            .zip(1..)
+            .map(|(champion, id)| participant(id, champion, if id <= 5 { 200 } else { 100 }))
+            .collect::<Vec<_>>();
+        // riven borrows the enum strings so it can't come from a Value
+        let json = json!({
+            "metadata": {"dataVersion": "2", "matchId": "EUW1_1", "participants": []},
+            "info": {
+                "gameCreation": 0, "gameDuration": 1800, "gameId": 1, "gameMode": "CLASSIC", "gameName": "",
+                "gameStartTimestamp": 0, "gameType": "MATCHED_GAME", "gameVersion": "14.10.1", "mapId": 11,
+                "participants": participants, "platformId": "EUW1", "queueId": 420, "teams": [],
+            },
+        });
+        serde_json::from_str(&json.to_string()).unwrap()
+    }
+
+    #[test]
+    fn diffs_and_events() {
+        let timeline: MatchTimeline = serde_json::from_value(json!({
+            "metadata": {"dataVersion": "2", "matchId": "EUW1_1", "participants": []},
+            "info": {
+                "frameInterval": 60_000,
+                "frames": [
+                    frame(0, 500, 500, json!([])),
+                    frame(1, 700, 600, json!([
+                        {"type": "CHAMPION_KILL", "timestamp": 61_000, "killerId": 7, "victimId": 2, "assistingParticipantIds": [8, 9]},
+                        {"type": "CHAMPION_KILL", "timestamp": 61_500, "killerId": 0, "killerTeamId": 200, "victimId": 8},
+                        {"type": "CHAMPION_KILL", "timestamp": 61_800, "killerId": 3, "victimId": 12},
+                        {"type": "ELITE_MONSTER_KILL", "timestamp": 62_000, "killerId": 8, "killerTeamId": 100, "monsterType": "DRAGON", "monsterSubType": "FIRE_DRAGON"},
+                        {"type": "ELITE_MONSTER_KILL", "timestamp": 63_000, "killerId": 2, "killerTeamId": 200, "monsterType": "HORDE"},
+                        {"type": "BUILDING_KILL", "timestamp": 64_000, "killerId": 6, "teamId": 200, "buildingType": "TOWER_BUILDING", "laneType": "MID_LANE", "towerType": "OUTER_TURRET"},
+                        {"type": "BUILDING_KILL", "timestamp": 65_000, "killerId": 0, "teamId": 100, "buildingType": "INHIBITOR_BUILDING", "laneType": "BOT_LANE"},
+                        {"type": "WARD_PLACED", "timestamp": 66_000, "creatorId": 1},
+                    ])),
+                ],
+            },
+        }))
+        .unwrap();
+
+        let t = GameTimeline::new(&timeline, &game());
+        assert_eq!(t.match_id, "EUW1_1");
+        // blue ahead is positive
+        assert_eq!(t.gold, [(0.0, 0.0), (1.0, 500.0)]);
+        assert_eq!(t.xp[1], (1.0, 500.0));
+
+        let events = t.events.iter().map(|e| (e.kind, e.team)).collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            [
+                (EventKind::Kill, Team::BLUE),
+                (EventKind::Kill, Team::RED),
+                (EventKind::Kill, Team::RED),
+                (EventKind::Dragon, Team::BLUE),
+                (EventKind::Monster, Team::RED),
+                (EventKind::Tower, Team::BLUE),
+                (EventKind::Inhibitor, Team::RED),
+            ]
+        );
+        assert_eq!(t.events[0].text, "Viego killed LeeSin +2");
+        assert_eq!(t.events[1].text, "minions killed Zed");
+        assert_eq!(t.events[2].text, "Ahri killed #12");
+        assert_eq!(t.events[3].text, "fire dragon");
+        assert_eq!(t.events[5].text, "mid lane outer turret");
+    }
+}
@@ -1,161 +1,595 @@
+use std::{future::Future, path::PathBuf};
+
+use clap::{builder::{PossibleValuesParser, TypedValueParser}, CommandFactory, Parser, Subcommand, ValueHint};
+use futures::{stream, StreamExt};
 use crossterm::style::Stylize;
-use riven::{consts::PlatformRoute, RiotApiError};
+use riven::{consts::{PlatformRoute, Queue}, models::{league_v4::LeagueEntry, match_v5::Match}};
+use serde::Serialize;
+
+use crate::{CONFIG_EXIT, USAGE_EXIT, completions::{generate, Shell}, config::{config_path, Config}, recent, roster, static_data::StaticData, summary::Summary, utils::{ROUTE_NAMES, parse_route, parse_queue, parse_time},
+    display::{SummonerDisplay, With, LeagueEntryDisplay, ChampionMasteryDisplay, LiveGameDisplay, MatchDisplay, ScoreboardDisplay, SummaryDisplay},
+    api::{api::{get_summoner, get_rank, get_masteries, get_games, get_live_game, get_match, MatchQuery}, cache::CacheMode, client::WatcherClient, player::Player}, error::WatcherError,
+    output::{CacheJson, CacheKindJson, ErrorJson, Format, GameJson, LiveJson, MasteryJson, Output, RankJson, SummaryJson, SummonerJson, Table}, };
+
+/// `--help` footer, from the codes in error.rs and main.rs so it can't go stale
+fn exit_codes() -> String {
+    let codes = [(0, "ok")]
+        .into_iter()
+        .chain(WatcherError::exit_codes())
+        .chain([(USAGE_EXIT, "bad usage"), (CONFIG_EXIT, "bad config.toml or no API key")])
+        .map(|(code, what)| format!("{} {}", code, what))
+        .collect::<Vec<_>>();
+    let lines = codes.chunks(4).map(|line| format!("  {}", line.join("  "))).collect::<Vec<_>>();
+    format!("Exit codes (of the first failed player, the rest still run):\n{}", lines.join("\n"))
+}
+
+const MATCH_TYPES: [&str; 4] = ["ranked", "normal", "tourney", "tutorial"];
+
+/// League of Legends stats from the terminal, runs the TUI without a command
+#[derive(Debug, Parser)]
+#[command(name = "watcher", version, after_help = exit_codes())]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
 
-use crate::{utils::{ROUTE_NAMES, parse_route, print_help, is_numeric}, 
-    display::{SummonerDisplay, With, LeagueEntryDisplay, ChampionMasteryDisplay, MatchDisplay}, 
-    api::api::{get_summoner, get_rank, get_masteries, get_games}, };
+    /// region of the players, `region` from the config or kr when not given
+    #[arg(long, global = true, value_parser = PossibleValuesParser::new(ROUTE_NAMES).map(|f: String| parse_route(f)))]
+    pub region: Option<PlatformRoute>,
 
-const GET_SUMMONER_ERR: &str = "couldn't get_summoner";
-const SUMMONER_IS_NONE: &str = "summoner is none";
-const GET_RANK_ERR: &str = "couldn't get rank";
-const GET_MASTERIES_ERR: &str = "couldn't get masteries";
-const GET_GAMES_ERR: &str = "couldn't get games";
+    /// how results are printed
+    #[arg(long, global = true, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
 
-#[derive(Debug)]
-#[allow(dead_code)]
-pub enum Arg {
-    Route(PlatformRoute),
-    Indent(String),
-    Int(usize),
+    /// Riot API key, overrides RGAPI_KEY and config
+    #[arg(long, global = true, value_name = "KEY")]
+    pub api_key: Option<String>,
 
-    HelpFlag,
+    /// don't read or write the response cache
+    #[arg(long, global = true, conflicts_with = "refresh")]
+    pub no_cache: bool,
 
-    SummonerFlag,
-    RankFlag,
-    MasteryFlag,
-    GameFlag(usize)
-    
+    /// ignore cached responses and store fresh ones
+    #[arg(long, global = true)]
+    pub refresh: bool,
 }
 
-#[derive(Debug)]
-pub struct Args {
-    pub args: Vec<Arg>,
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// summoner level and Riot ID
+    Summoner {
+        #[command(flatten)]
+        players: Players,
+    },
+    /// ranked queues
+    Rank {
+        #[command(flatten)]
+        players: Players,
+    },
+    /// game in progress with ranks, runes and bans
+    Live {
+        #[command(flatten)]
+        players: Players,
+    },
+    /// highest champion masteries
+    Mastery {
+        #[command(flatten)]
+        players: Players,
+        /// how many champions
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+    },
+    /// scoreboards of the match history
+    Games {
+        #[command(flatten)]
+        players: Players,
+        /// only the n-th game of the history, 0 is the most recent
+        #[arg(short, long)]
+        index: Option<usize>,
+        /// expanded scoreboard with items, runes, spells, damage, gold and vision
+        #[arg(long)]
+        full: bool,
+        #[command(flatten)]
+        query: QueryArgs,
+    },
+    /// match history with one row per participant, meant for --format csv
+    History {
+        #[command(flatten)]
+        players: Players,
+        #[command(flatten)]
+        query: QueryArgs,
+    },
+    /// win rate, kda, champions, roles, best/worst games and streak over the match history
+    Summary {
+        #[command(flatten)]
+        players: Players,
+        #[command(flatten)]
+        query: QueryArgs,
+    },
+    /// one match by id, e.g. EUW1_6500000000
+    Match {
+        id: String,
+        /// expanded scoreboard with items, runes, spells, damage, gold and vision
+        #[arg(long)]
+        full: bool,
+    },
+    /// cache dir size
+    Cache,
+    /// names of items, runes, spells, queues and maps from a Data Dragon snapshot
+    Ddragon {
+        #[command(subcommand)]
+        action: Ddragon,
+    },
+    /// terminal UI, same as no command
+    Tui,
+    /// completion script, e.g. `watcher completions bash > ~/.local/share/bash-completion/completions/watcher`
+    Completions {
+        shell: Shell,
+    },
+    /// recently searched summoners, used by the completion scripts
+    #[command(hide = true)]
+    Recent,
 }
 
+#[derive(Debug, Subcommand)]
+pub enum Ddragon {
+    /// read champion, item, runesReforged, summoner, queues and maps json from `dir` or below it
+    Import {
+        #[arg(value_hint = ValueHint::DirPath)]
+        dir: PathBuf,
+    },
+}
+
+/// who a command looks up
+#[derive(Debug, clap::Args)]
+pub struct Players {
+    /// Riot IDs, plain summoner names or @profile
+    #[arg(value_name = "NAME#TAG", required_unless_present = "from")]
+    pub names: Vec<String>,
+    /// roster with one `name[#tag] [region]` per line, `-` for stdin
+    #[arg(long, value_name = "FILE", value_hint = ValueHint::FilePath)]
+    pub from: Option<PathBuf>,
+}
 
-impl Args {
-    pub fn new(input: Vec<String>) -> Args{
-        let args = Args::parse(input);
-        Args { args }
+/// match-v5 filters shared by `games` and `history`
+#[derive(Debug, clap::Args)]
+pub struct QueryArgs {
+    /// skip the n most recent games
+    #[arg(long, default_value_t = 0)]
+    pub start: usize,
+    /// how many games to fetch
+    #[arg(long, default_value_t = 20)]
+    pub count: usize,
+    /// queue id or solo, flex, draft, blind, aram, clash
+    #[arg(long, value_parser = |v: &str| parse_queue(v).ok_or("unknown queue"))]
+    pub queue: Option<Queue>,
+    /// ranked, normal, tourney or tutorial
+    #[arg(long = "type", value_parser = PossibleValuesParser::new(MATCH_TYPES))]
+    pub match_type: Option<String>,
+    /// unix seconds or YYYY-MM-DD
+    #[arg(long, value_name = "TIME", value_parser = |v: &str| parse_time(v).ok_or("expected unix seconds or YYYY-MM-DD"))]
+    pub start_time: Option<i64>,
+    /// unix seconds or YYYY-MM-DD
+    #[arg(long, value_name = "TIME", value_parser = |v: &str| parse_time(v).ok_or("expected unix seconds or YYYY-MM-DD"))]
+    pub end_time: Option<i64>,
+}
+
+impl QueryArgs {
+    fn query(&self) -> MatchQuery {
+        MatchQuery {
+            start: self.start,
+            count: self.count,
+            queue: self.queue,
+            match_type: self.match_type.clone(),
+            start_time: self.start_time,
+            end_time: self.end_time,
+        }
     }
+}
 
-    fn parse(args: Vec<String>) -> Vec<Arg>{
-        let mut a: Vec<Arg> = Vec::new();
-        for arg in  args {
-            a.push(match arg.as_str() {
-                "-h" | "--help" => Arg::HelpFlag,
-                "-s" | "--sum" => Arg::SummonerFlag,
-                "-r" | "--rank" => Arg::RankFlag,
-                "-m" | "-mastery" => Arg::MasteryFlag,
-                "-g" | "-game" =>   Arg::GameFlag(arg.parse::<usize>().unwrap_or(0)),
-                arg if is_numeric(arg) =>  Arg::Int(arg.parse::<usize>().unwrap_or(0)),
-                arg if ROUTE_NAMES.contains(&arg) => Arg::Route(parse_route(arg.into())),
-                _ =>  Arg::Indent(arg.into())
-            });
-        }    
-        a
+impl Cli {
+    pub fn cache_mode(&self) -> CacheMode {
+        match (self.no_cache, self.refresh) {
+            (true, _) => CacheMode::Off,
+            (_, true) => CacheMode::Refresh,
+            _ => CacheMode::Normal,
+        }
     }
 
-    pub async fn  execute(&self, api_key: &str) -> Result<(), RiotApiError>{
-        let route: Option<PlatformRoute> = self.args.iter().find_map(|f| match *f {
-            Arg::Route(route) => Some(route),
-            _ => None,
-        }); 
-        
-        let mut names: Vec<String> = self.args.iter().map(|f| {
-        match f {
-           Arg::Indent(ident)  => ident.into(),
-           _ => "".into(), 
-        }}).collect::<Vec<String>>();
-        names.retain(|f| f != "");
-        
-        println!("players [{:?}] {}",route, 
-                 names.iter()
-                 .map(|f| f.clone()
-                      .with(crossterm::style::Color::Green)
-                      .to_string()).collect::<Vec<String>>().join(" "));
-
-        for arg in &self.args {
-            match arg {
-                Arg::HelpFlag => print_help(),
-                Arg::SummonerFlag =>  print_summoner(api_key.into(),route.unwrap_or(PlatformRoute::KR), names.clone()).await,
-                Arg::RankFlag => print_rank(api_key.into(),route.unwrap_or(PlatformRoute::KR), names.clone()).await,
-                Arg::MasteryFlag => print_mastery(api_key.into(),route.unwrap_or(PlatformRoute::KR), names.clone()).await,
-                Arg::GameFlag(game) => {
-                            print_game(api_key.into(),route.unwrap_or(PlatformRoute::KR), names.clone(), *game).await
-                },
-                _ => {}
+    /// runs the command for every player, returns the failures,
+    /// Err when the roster can't be read and nothing was looked up
+    pub async fn execute(&self, client: &WatcherClient, config: &Config) -> Result<Vec<Failure>, WatcherError> {
+        let route = self.region.unwrap_or_else(|| config.default_route());
+        let mut out = Output::new(self.format);
+        let mut failed = vec![];
+
+        let names = match self.command.as_ref().and_then(Command::players) {
+            Some(players) => players.names().map_err(WatcherError::Io)?,
+            None => vec![],
+        };
+        let targets = resolve_targets(&names, route, config, &mut failed, &mut out);
+        if self.format == Format::Text && !targets.is_empty() {
+            println!("players {}",
+                     targets.iter()
+                     .map(|f| format!("{} [{}]", f.name.clone().with(crossterm::style::Color::Green), f.route))
+                     .collect::<Vec<String>>().join(" "));
+        }
+
+        match &self.command {
+            Some(Command::Summoner { .. }) => print_summoner(client, &targets, &mut out, &mut failed).await,
+            Some(Command::Rank { .. }) => print_rank(client, &targets, &mut out, &mut failed).await,
+            Some(Command::Live { .. }) => print_live(client, &targets, &mut out, &mut failed).await,
+            Some(Command::Mastery { top, .. }) => print_mastery(client, &targets, *top, &mut out, &mut failed).await,
+            Some(Command::Games { index, full, query, .. }) => print_games(client, &targets, &query.query(), *index, *full, &mut out, &mut failed).await,
+            Some(Command::History { query, .. }) => print_history(client, &targets, &query.query(), &mut out, &mut failed).await,
+            Some(Command::Summary { query, .. }) => print_summary(client, &targets, &query.query(), &mut out, &mut failed).await,
+            Some(Command::Match { id, full }) => print_match(client, route, id, *full, &mut out, &mut failed).await,
+            Some(Command::Cache) => print_cache(client, &mut out),
+            Some(Command::Tui) | Some(Command::Completions { .. }) | Some(Command::Recent) | Some(Command::Ddragon { .. }) | None => {},
+        };
+        out.finish();
+
+        // only a single player from the command line counts as a search
+        let typed = self.command.as_ref().and_then(Command::players).map(|p| p.from.is_none()).unwrap_or(false);
+        if let ([target], true, true) = (targets.as_slice(), typed, failed.is_empty()) {
+            recent::push(&target.name, target.route);
+        }
+
+        if names.len() > 1 && !failed.is_empty() {
+            eprintln!("{} {}/{} failed", "summary".with(crossterm::style::Color::Red), failed.len(), names.len());
+            for (target, err) in &failed {
+                eprintln!("    {} [{}]: {}", target.name, target.route, err);
             }
         }
+        Ok(failed)
+    }
+}
 
-        Ok(())
+impl Players {
+    /// names from the command line then the roster, with the region given per line
+    fn names(&self) -> Result<Vec<(String, Option<PlatformRoute>)>, String> {
+        let mut names: Vec<(String, Option<PlatformRoute>)> = self.names.iter().map(|f| (f.clone(), None)).collect();
+        if let Some(path) = &self.from {
+            names.extend(roster::parse(&roster::read(path)?));
+        }
+        Ok(names)
     }
 }
 
+/// commands that run before the API key is resolved, true when one ran
+pub fn run_offline(command: &Option<Command>) -> bool {
+    match command {
+        Some(Command::Completions { shell }) => {
+            let bin = std::env::args()
+                .next()
+                .and_then(|a| std::path::Path::new(&a).file_name().map(|f| f.to_string_lossy().to_string()))
+                .unwrap_or_else(|| "watcher".to_string());
+            print!("{}", generate(*shell, Cli::command(), &bin));
+            true
+        }
+        Some(Command::Recent) => {
+            let config = Config::load().unwrap_or_default();
+            for name in config.profiles.keys() {
+                println!("@{}", name);
+            }
+            for (name, _) in recent::load() {
+                println!("{}", name);
+            }
+            true
+        }
+        Some(Command::Ddragon { action: Ddragon::Import { dir } }) => {
+            match StaticData::import(dir).and_then(|data| data.save().map(|path| (data, path))) {
+                Ok((data, path)) => println!(
+                    "{} {}: {} champions, {} items, {} runes, {} spells, {} queues, {} maps saved to {}",
+                    "imported".with(crossterm::style::Color::Green),
+                    data.version.as_deref().unwrap_or("ddragon"),
+                    data.champions.len(),
+                    data.items.len(),
+                    data.runes.len(),
+                    data.spells.len(),
+                    data.queues.len(),
+                    data.maps.len(),
+                    path.display()
+                ),
+                Err(err) => {
+                    let err = WatcherError::Io(err);
+                    eprintln!("{} {}", "ERR".with(crossterm::style::Color::Red), err);
+                    std::process::exit(err.exit_code());
+                }
+            }
+            true
+        }
+        _ => false,
+    }
+}
 
-async fn print_summoner(api_key: String, route: PlatformRoute, names: Vec<String>) {
-     for name in names{
-                let sum = get_summoner(&api_key, route, &name)
-                    .await
-                    .expect(&GET_SUMMONER_ERR)
-                    .expect(&SUMMONER_IS_NONE);
-                println!("{}", SummonerDisplay::with(sum));
-     }
-}
-
-async fn print_rank(api_key: String, route: PlatformRoute, names: Vec<String>) {
-    for name in names {
-                let id = get_summoner(&api_key, route, &name)
-                    .await
-                    .expect(&GET_SUMMONER_ERR)
-                    .expect(&SUMMONER_IS_NONE)
-                    .id;
-                let res = get_rank(&api_key, route, id.as_str())
-                    .await
-                    .expect(GET_RANK_ERR);
-                let ranks: Vec<LeagueEntryDisplay> = res
-                    .iter()
-                    .map(|f| LeagueEntryDisplay::with(f.clone()))
-                    .collect();
-                for r in ranks {
-                    print!("{}", r);
+impl Command {
+    fn players(&self) -> Option<&Players> {
+        match self {
+            Command::Summoner { players }
+            | Command::Rank { players }
+            | Command::Live { players }
+            | Command::Mastery { players, .. }
+            | Command::Games { players, .. }
+            | Command::History { players, .. }
+            | Command::Summary { players, .. } => Some(players),
+            _ => None,
+        }
+    }
+}
+
+/// a player to look up and where
+#[derive(Debug, Clone)]
+pub struct Target {
+    pub name: String,
+    pub route: PlatformRoute,
+}
+
+/// a player that couldn't be looked up and why
+pub type Failure = (Target, WatcherError);
+
+/// `@name` is looked up in the config profiles, a region on the roster line wins over the
+/// profile's which wins over --region, unknown profiles are reported like players that weren't found
+fn resolve_targets(names: &[(String, Option<PlatformRoute>)], route: PlatformRoute, config: &Config, failed: &mut Vec<Failure>, out: &mut Output) -> Vec<Target> {
+    let mut targets = vec![];
+    for (name, line_route) in names {
+        match name.strip_prefix('@') {
+            None => targets.push(Target { name: name.clone(), route: line_route.unwrap_or(route) }),
+            Some(profile) => match config.profile(profile) {
+                Some(p) => targets.push(Target { name: p.riot_id, route: line_route.or(p.route).unwrap_or(route) }),
+                None => {
+                    let target = Target { name: name.clone(), route: line_route.unwrap_or(route) };
+                    let err = WatcherError::NotFound(format!("profile {} in {}", name, config_path().unwrap_or_default().display()));
+                    report(target, err, failed, out);
                 }
+            },
+        }
     }
+    targets
 }
 
-async fn print_mastery(api_key: String, route: PlatformRoute, names: Vec<String>) {
-    for name in names {
-let id = get_summoner(&api_key, route, &name)
-                    .await
-                    .expect(&GET_SUMMONER_ERR)
-                    .expect(&SUMMONER_IS_NONE)
-                    .id;
-                let masteries: Vec<ChampionMasteryDisplay> = get_masteries(&api_key, route, &id, 10)
-                    .await
-                    .expect(GET_MASTERIES_ERR)
-                    .iter()
-                    .map(|f| ChampionMasteryDisplay::with(f.clone()))
-                    .collect();
-                for m in masteries {
-                    println!("{}", m);
+/// prints the failure of one player and keeps it for the summary and exit code,
+/// json output also gets an `error` record
+fn report(target: Target, err: WatcherError, failed: &mut Vec<Failure>, out: &mut Output) {
+    eprintln!("{} {}: {}", "ERR".with(crossterm::style::Color::Red), target.name, err);
+    if out.format != Format::Text {
+        out.emit("error", &target.name, &target.route.to_string(), ErrorJson::from(&err), "");
+    }
+    failed.push((target, err));
+}
+
+/// one record a printer made for a target
+struct Found<T> {
+    player: String,
+    data: T,
+    text: String,
+}
+
+/// looks up `batch_concurrency` targets at once through the shared client,
+/// records are printed in the given order as soon as they're ready
+async fn run<'a, T, F, Fut>(client: &WatcherClient, targets: &'a [Target], kind: &'static str, out: &mut Output, failed: &mut Vec<Failure>, fetch: F)
+where
+    T: Serialize + Table,
+    F: Fn(&'a Target) -> Fut,
+    Fut: Future<Output = Result<Vec<Found<T>>, WatcherError>>,
+{
+    let mut results = stream::iter(targets)
+        .map(|target| {
+            let res = fetch(target);
+            async move { (target, res.await) }
+        })
+        .buffered(client.batch_concurrency);
+    while let Some((target, res)) = results.next().await {
+        match res {
+            Ok(found) => {
+                for f in found {
+                    out.emit(kind, &f.player, &target.route.to_string(), f.data, f.text);
                 }
+            }
+            Err(err) => report(target.clone(), err, failed, out),
+        }
+    }
+}
+
+async fn find_summoner(client: &WatcherClient, route: PlatformRoute, name: &str) -> Result<Player, WatcherError> {
+    get_summoner(client, route, name)
+        .await?
+        .ok_or_else(|| WatcherError::NotFound(format!("{} [{}]", name, route)))
+}
+
+fn print_cache(client: &WatcherClient, out: &mut Output) {
+    let dir = match &client.cache.dir {
+        Some(dir) => dir,
+        None => {
+            eprintln!("{}", "no cache dir".with(crossterm::style::Color::Red));
+            return;
+        }
+    };
+    let size = client.cache.size();
+    let mut text = format!("{}\n", dir.display().to_string().with(crossterm::style::Color::Blue));
+    for (kind, files, bytes) in &size {
+        text += &format!("    {} {:>6} files {:>10}\n", format!("{:<10}", kind).with(crossterm::style::Color::Yellow), files, human_bytes(*bytes));
+    }
+    let total: u64 = size.iter().map(|f| f.2).sum();
+    text += &format!("    {} {:>23}\n", format!("{:<10}", "total").with(crossterm::style::Color::Cyan), human_bytes(total));
+
+    let data = CacheJson {
+        dir: dir.display().to_string(),
+        total_bytes: total,
+        kinds: size.iter().map(|(kind, files, bytes)| CacheKindJson {
+            kind: kind.to_string(),
+            files: *files,
+            bytes: *bytes,
+        }).collect(),
+    };
+    out.emit("cache", "", "", data, text);
+}
+
+fn human_bytes(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1 << 20 => format!("{:.1} MiB", b as f64 / (1 << 20) as f64),
+        b if b >= 1 << 10 => format!("{:.1} KiB", b as f64 / (1 << 10) as f64),
+        b => format!("{} B", b),
+    }
+}
+
+
+async fn print_summoner(client: &WatcherClient, targets: &[Target], out: &mut Output, failed: &mut Vec<Failure>) {
+    run(client, targets, "summoner", out, failed, |target| async move {
+        let player = find_summoner(client, target.route, &target.name).await?;
+        Ok(vec![Found {
+            player: player.name(),
+            data: SummonerJson::from(&player),
+            text: format!("{}\n", SummonerDisplay::with(player.clone())),
+        }])
+    }).await
+}
+
+async fn print_rank(client: &WatcherClient, targets: &[Target], out: &mut Output, failed: &mut Vec<Failure>) {
+    run(client, targets, "rank", out, failed, |target| async move {
+        let player = find_summoner(client, target.route, &target.name).await?;
+        let entries: Vec<LeagueEntry> = get_rank(client, target.route, &player.summoner.id)
+            .await?
+            .into_iter()
+            .map(|f| LeagueEntry { summoner_name: player.name(), ..f })
+            .collect();
+        let text = entries
+            .iter()
+            .map(|f| LeagueEntryDisplay::with(f.clone()).to_string())
+            .collect::<String>();
+        let data: Vec<RankJson> = entries.iter().map(RankJson::from).collect();
+        Ok(vec![Found { player: player.name(), data, text }])
+    }).await
+}
+
+async fn print_live(client: &WatcherClient, targets: &[Target], out: &mut Output, failed: &mut Vec<Failure>) {
+    run(client, targets, "live", out, failed, |target| async move {
+        let player = find_summoner(client, target.route, &target.name).await?;
+        let live = get_live_game(client, target.route, &player.summoner.puuid)
+            .await?
+            .ok_or_else(|| WatcherError::NotFound(format!("{} is not in a game", player.name())))?;
+        let text = format!("{}\n", LiveGameDisplay::with(live.clone()));
+        Ok(vec![Found { player: player.name(), data: LiveJson::from(&live), text }])
+    }).await
+}
+
+async fn print_mastery(client: &WatcherClient, targets: &[Target], top: usize, out: &mut Output, failed: &mut Vec<Failure>) {
+    run(client, targets, "mastery", out, failed, |target| async move {
+        let player = find_summoner(client, target.route, &target.name).await?;
+        let masteries = get_masteries(client, target.route, &player.summoner.id, top).await?;
+        let text = masteries
+            .iter()
+            .map(|f| format!("{}\n", ChampionMasteryDisplay::with(f.clone())))
+            .collect::<String>();
+        let data: Vec<MasteryJson> = masteries.iter().map(MasteryJson::from).collect();
+        Ok(vec![Found { player: player.name(), data, text }])
+    }).await
+}
+
+/// every game of the query or only the `index`-th one
+async fn print_games(client: &WatcherClient, targets: &[Target], query: &MatchQuery, index: Option<usize>, full: bool, out: &mut Output, failed: &mut Vec<Failure>) {
+    run(client, targets, "game", out, failed, |target| async move {
+        let player = find_summoner(client, target.route, &target.name).await?;
+        let games = get_games(client, target.route, &player.summoner.puuid, query).await?;
+        for (id, err) in &games.failed {
+            eprintln!("{} {}: {}", "couldn't get game".with(crossterm::style::Color::Red), id, err);
+        }
+        let len = games.matches.len();
+        let matches = match index {
+            Some(index) => vec![games
+                .matches
+                .into_iter()
+                .nth(index)
+                .ok_or(WatcherError::BadIndex { index, len })?],
+            None => games.matches,
+        };
+        Ok(matches
+            .into_iter()
+            .map(|m| Found {
+                player: player.name(),
+                data: GameJson::new(&m, &player.summoner.puuid),
+                text: format!("{}\n\n", scoreboard(m, full)),
+            })
+            .collect())
+    }).await
+}
+
+fn scoreboard(m: Match, full: bool) -> String {
+    match full {
+        true => ScoreboardDisplay::with(m).to_string(),
+        false => MatchDisplay::with(m).to_string(),
     }
 }
-async fn print_game(api_key: String, route: PlatformRoute, names: Vec<String>, game: usize) {
-    for name in names {
-    let id = get_summoner(&api_key, route, &name)
-                    .await
-                    .expect(&GET_SUMMONER_ERR)
-                    .expect(&SUMMONER_IS_NONE)
-                    .puuid;
-                let matches = get_games(&api_key, route, &id).await.expect(&GET_GAMES_ERR);
-                let m = MatchDisplay::with(
-                    matches
-                        .get(game)
-                        .unwrap()
-                        .clone(),
-                );
-                println!("{}", m);
+
+/// the region is taken from the id prefix when it has one
+async fn print_match(client: &WatcherClient, route: PlatformRoute, id: &str, full: bool, out: &mut Output, failed: &mut Vec<Failure>) {
+    let route = id
+        .split_once('_')
+        .and_then(|(prefix, _)| prefix.parse::<PlatformRoute>().ok())
+        .unwrap_or(route);
+    match get_match(client, route, id).await {
+        Ok(m) => {
+            let data = GameJson::new(&m, "");
+            let text = format!("{}\n", scoreboard(m, full));
+            out.emit("game", "", &route.to_string(), data, text);
+        }
+        Err(err) => report(Target { name: id.to_string(), route }, err, failed, out),
     }
+}
+
+async fn print_summary(client: &WatcherClient, targets: &[Target], query: &MatchQuery, out: &mut Output, failed: &mut Vec<Failure>) {
+    run(client, targets, "summary", out, failed, |target| async move {
+        let player = find_summoner(client, target.route, &target.name).await?;
+        let games = get_games(client, target.route, &player.summoner.puuid, query).await?;
+        for (id, err) in &games.failed {
+            eprintln!("{} {}: {}", "couldn't get game".with(crossterm::style::Color::Red), id, err);
+        }
+        let summary = Summary::new(&games.matches, &player.summoner.puuid);
+        let data = SummaryJson::from(&summary);
+        let text = format!("{}\n", SummaryDisplay::with(summary));
+        Ok(vec![Found { player: player.name(), data, text }])
+    }).await
+}
 
+/// every game of the query, one row per participant
+async fn print_history(client: &WatcherClient, targets: &[Target], query: &MatchQuery, out: &mut Output, failed: &mut Vec<Failure>) {
+    run(client, targets, "history", out, failed, |target| async move {
+        let player = find_summoner(client, target.route, &target.name).await?;
+        let games = get_games(client, target.route, &player.summoner.puuid, query).await?;
+        for (id, err) in &games.failed {
+            eprintln!("{} {}: {}", "couldn't get game".with(crossterm::style::Color::Red), id, err);
+        }
+        let data: Vec<GameJson> = games
+            .matches
+            .iter()
+            .map(|m| GameJson::new(m, &player.summoner.puuid))
+            .collect();
+        let text = history_text(&data, &player.summoner.puuid);
+        Ok(vec![Found { player: player.name(), data, text }])
+    }).await
+}
+
+/// same columns as the csv, searched player in green
+fn history_text(games: &[GameJson], puuid: &str) -> String {
+    let mut text = String::new();
+    for game in games {
+        let rows = game.rows();
+        if let Some(first) = rows.first() {
+            // match id, date, queue, duration
+            text += &format!("{}  {}  {}  {}s\n", first[0].clone().with(crossterm::style::Color::Blue), first[1], first[2], first[3]);
+        }
+        for (row, p) in rows.iter().zip(&game.participants) {
+            let line = format!("    {:<30}{:<6}{:<14}{:<9}", row[4], row[5], row[6], row[7])
+                + &row[8..]
+                    .iter()
+                    .map(|f| format!("{:<8}", f))
+                    .collect::<String>();
+            let line = if p.puuid == puuid {
+                line.with(crossterm::style::Color::Green)
+            } else {
+                line.with(crossterm::style::Color::Reset)
+            };
+            text += &format!("{}\n", line);
+        }
+    }
+    text
 }
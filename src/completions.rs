@@ -0,0 +1,455 @@
+use clap::{Arg, Command, ValueEnum, ValueHint};
+
+/// subcommand that prints the recent summoner names, called by the scripts
+pub const RECENT_COMMAND: &str = "recent";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// what a script needs to know about one flag
+struct Flag {
+    long: Option<String>,
+    short: Option<char>,
+    help: String,
+    takes_value: bool,
+    /// completed with file names
+    file: bool,
+    values: Vec<String>,
+}
+
+/// a positional, `names` are completed from the search history
+struct Positional {
+    name: String,
+    many: bool,
+    values: Vec<String>,
+}
+
+struct Sub {
+    name: String,
+    about: String,
+    flags: Vec<Flag>,
+    positionals: Vec<Positional>,
+    /// nested ones like `ddragon import`, offered instead of the positionals
+    subs: Vec<Sub>,
+}
+
+impl Positional {
+    fn is_names(&self) -> bool {
+        self.name == "names"
+    }
+}
+
+/// completion script for `bin`, built from the clap definition so new flags show up on their own
+pub fn generate(shell: Shell, mut cmd: Command, bin: &str) -> String {
+    cmd.build();
+    let top = flags(&cmd);
+    let subs = subs(&cmd);
+
+    match shell {
+        Shell::Bash => bash(bin, &top, &subs),
+        Shell::Zsh => zsh(bin, &top, &subs),
+        Shell::Fish => fish(bin, &top, &subs),
+    }
+}
+
+fn subs(cmd: &Command) -> Vec<Sub> {
+    cmd.get_subcommands()
+        .filter(|s| !s.is_hide_set() && s.get_name() != "help")
+        .map(|s| Sub {
+            name: s.get_name().to_string(),
+            about: s.get_about().map(|a| a.to_string()).unwrap_or_default(),
+            flags: flags(s),
+            positionals: s
+                .get_positionals()
+                .map(|a| Positional {
+                    name: a.get_id().to_string(),
+                    many: a.get_num_args().map(|n| n.max_values() > 1).unwrap_or(false),
+                    values: values(a),
+                })
+                .collect(),
+            subs: subs(s),
+        })
+        .collect()
+}
+
+/// every subcommand at any depth with the names leading to it, parents first
+fn flatten<'a>(subs: &'a [Sub], parents: &[&'a str], out: &mut Vec<(Vec<&'a str>, &'a Sub)>) {
+    for sub in subs {
+        let mut path = parents.to_vec();
+        path.push(&sub.name);
+        out.push((path.clone(), sub));
+        flatten(&sub.subs, &path, out);
+    }
+}
+
+fn flags(cmd: &Command) -> Vec<Flag> {
+    cmd.get_arguments()
+        .filter(|a| !a.is_positional() && !a.is_hide_set())
+        .map(|a| Flag {
+            long: a.get_long().map(|l| l.to_string()),
+            short: a.get_short(),
+            help: a
+                .get_help()
+                .map(|h| h.to_string())
+                .unwrap_or_default()
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            takes_value: a.get_action().takes_values(),
+            file: a.get_value_hint() == ValueHint::FilePath,
+            values: values(a),
+        })
+        .collect()
+}
+
+fn values(arg: &Arg) -> Vec<String> {
+    arg.get_possible_values()
+        .iter()
+        .filter(|v| !v.is_hide_set())
+        .map(|v| v.get_name().to_string())
+        .collect()
+}
+
+fn flag_words(flags: &[Flag]) -> String {
+    flags
+        .iter()
+        .flat_map(|f| {
+            f.long
+                .iter()
+                .map(|l| format!("--{}", l))
+                .chain(f.short.iter().map(|s| format!("-{}", s)))
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// strips what would end a quoted zsh/fish description
+fn clean(help: &str) -> String {
+    help.replace(['\'', '[', ']', ':', '`'], "")
+}
+
+fn bash(bin: &str, top: &[Flag], subs: &[Sub]) -> String {
+    let func = format!("_{}", bin.replace('-', "_"));
+    let names = subs.iter().map(|s| s.name.as_str()).collect::<Vec<_>>();
+    // nested subcommands are matched as `ddragon_import`
+    let mut all = vec![];
+    flatten(subs, &[], &mut all);
+    let all = all.into_iter().map(|(path, sub)| (path.join("_"), sub)).collect::<Vec<_>>();
+
+    // every flag that takes a value, the same long name means the same values everywhere
+    let mut value_cases = String::new();
+    let mut files: Vec<String> = vec![];
+    let mut free: Vec<String> = vec![];
+    let mut seen: Vec<String> = vec![];
+    for flag in top.iter().chain(all.iter().flat_map(|(_, s)| &s.flags)) {
+        if !flag.takes_value {
+            continue;
+        }
+        let words = flag
+            .long
+            .iter()
+            .map(|l| format!("--{}", l))
+            .chain(flag.short.iter().map(|s| format!("-{}", s)))
+            .filter(|w| !seen.contains(w))
+            .collect::<Vec<_>>();
+        seen.extend(words.clone());
+        if words.is_empty() {
+            continue;
+        }
+        if flag.file {
+            files.extend(words);
+            continue;
+        }
+        if flag.values.is_empty() {
+            free.extend(words);
+            continue;
+        }
+        value_cases += &format!(
+            "        {})\n            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n            return ;;\n",
+            words.join("|"),
+            flag.values.join(" ")
+        );
+    }
+    if !files.is_empty() {
+        value_cases += &format!(
+            "        {})\n            COMPREPLY=($(compgen -f -- \"$cur\"))\n            return ;;\n",
+            files.join("|")
+        );
+    }
+    if !free.is_empty() {
+        value_cases += &format!("        {})\n            return ;;\n", free.join("|"));
+    }
+
+    let mut flag_cases = format!("            \"\") opts=\"{}\" ;;\n", flag_words(top));
+    let mut arg_cases = format!(
+        "        \"\") COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")) ;;\n",
+        names.join(" ")
+    );
+    for (key, sub) in &all {
+        flag_cases += &format!("            {}) opts=\"{}\" ;;\n", key, flag_words(&sub.flags));
+        if !sub.subs.is_empty() {
+            arg_cases += &format!(
+                "        {}) COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")) ;;\n",
+                key,
+                sub.subs.iter().map(|s| s.name.as_str()).collect::<Vec<_>>().join(" ")
+            );
+        } else if let Some(p) = sub.positionals.first() {
+            if p.is_names() {
+                arg_cases += &format!(
+                    "        {})\n            local IFS=$'\\n'\n            COMPREPLY=($(compgen -W \"$({} {} 2>/dev/null)\" -- \"$cur\")) ;;\n",
+                    key, bin, RECENT_COMMAND
+                );
+            } else if !p.values.is_empty() {
+                arg_cases += &format!(
+                    "        {}) COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")) ;;\n",
+                    key,
+                    p.values.join(" ")
+                );
+            }
+        }
+    }
+
+    format!(
+        r#"{func}() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    local cmd="" opts=""
+    for word in "${{COMP_WORDS[@]:1:COMP_CWORD-1}}"; do
+        case "${{cmd:+${{cmd}}_}}$word" in
+            {subs}) cmd="${{cmd:+${{cmd}}_}}$word" ;;
+        esac
+    done
+
+    case "$prev" in
+{value_cases}    esac
+
+    if [[ "$cur" == -* ]]; then
+        case "$cmd" in
+{flag_cases}        esac
+        COMPREPLY=($(compgen -W "$opts" -- "$cur"))
+        return
+    fi
+
+    case "$cmd" in
+{arg_cases}    esac
+}}
+complete -F {func} {bin}
+"#,
+        subs = all.iter().map(|(key, _)| key.as_str()).collect::<Vec<_>>().join("|"),
+    )
+}
+
+fn zsh_flags(flags: &[Flag]) -> String {
+    let mut specs = String::new();
+    for flag in flags {
+        let value = match (flag.takes_value, flag.values.is_empty()) {
+            (false, _) => String::new(),
+            (true, _) if flag.file => ": :_files".to_string(),
+            (true, true) => ": : ".to_string(),
+            (true, false) => format!(": :({})", flag.values.join(" ")),
+        };
+        let help = clean(&flag.help);
+        if let Some(long) = &flag.long {
+            let eq = if flag.takes_value { "=" } else { "" };
+            specs += &format!("        '--{}{}[{}]{}' \\\n", long, eq, help, value);
+        }
+        if let Some(short) = flag.short {
+            let plus = if flag.takes_value { "+" } else { "" };
+            specs += &format!("        '-{}{}[{}]{}' \\\n", short, plus, help, value);
+        }
+    }
+    specs
+}
+
+fn zsh(bin: &str, top: &[Flag], subs: &[Sub]) -> String {
+    let func = format!("_{}", bin.replace('-', "_"));
+    format!(
+        r#"#compdef {bin}
+
+{func}_names() {{
+    local -a names
+    names=("${{(@f)$({bin} {recent} 2>/dev/null)}}")
+    compadd -a names
+}}
+
+{functions}{func} "$@"
+"#,
+        recent = RECENT_COMMAND,
+        functions = zsh_command(&func, &func, top, subs),
+    )
+}
+
+/// `{func}` completing one command with subcommands, those with their own get `{func}_<name>`
+fn zsh_command(func: &str, names: &str, flags: &[Flag], subs: &[Sub]) -> String {
+    let commands = subs
+        .iter()
+        .map(|s| format!("        '{}:{}'\n", s.name, clean(&s.about)))
+        .collect::<String>();
+
+    let mut nested = String::new();
+    let mut cases = String::new();
+    for sub in subs {
+        if !sub.subs.is_empty() {
+            let sub_func = format!("{}_{}", func, sub.name.replace('-', "_"));
+            nested += &zsh_command(&sub_func, names, &sub.flags, &sub.subs);
+            cases += &format!("            {})\n                {}\n                ;;\n", sub.name, sub_func);
+            continue;
+        }
+        let mut specs = zsh_flags(&sub.flags);
+        for p in &sub.positionals {
+            let action = if p.is_names() {
+                format!("{}_names", names)
+            } else if !p.values.is_empty() {
+                format!("({})", p.values.join(" "))
+            } else {
+                " ".to_string()
+            };
+            let star = if p.many { "*" } else { "" };
+            specs += &format!("        '{}:{}:{}' \\\n", star, p.name, action);
+        }
+        cases += &format!(
+            "            {})\n                _arguments -s \\\n{}                ;;\n",
+            sub.name,
+            specs.replace("        '", "                    '")
+        );
+    }
+
+    format!(
+        r#"{func}() {{
+    local line state
+    local -a commands
+    commands=(
+{commands}    )
+
+    _arguments -C -s \
+{specs}        '1: :->command' \
+        '*:: :->args'
+
+    case $state in
+        command)
+            _describe 'command' commands
+            ;;
+        args)
+            case $line[1] in
+{cases}            esac
+            ;;
+    esac
+}}
+
+{nested}"#,
+        specs = zsh_flags(flags),
+    )
+}
+
+fn fish(bin: &str, top: &[Flag], subs: &[Sub]) -> String {
+    let mut lines = vec![format!("complete -c {} -f", bin)];
+    let fish_flag = |condition: &str, flag: &Flag| {
+        let mut line = format!("complete -c {} -n '{}'", bin, condition);
+        if let Some(long) = &flag.long {
+            line += &format!(" -l {}", long);
+        }
+        if let Some(short) = flag.short {
+            line += &format!(" -s {}", short);
+        }
+        if flag.file {
+            line += " -r -F";
+        } else if flag.takes_value {
+            line += " -x";
+            if !flag.values.is_empty() {
+                line += &format!(" -a '{}'", flag.values.join(" "));
+            }
+        }
+        line + &format!(" -d '{}'", clean(&flag.help))
+    };
+
+    for flag in top {
+        lines.push(fish_flag("__fish_use_subcommand", flag));
+    }
+    for sub in subs {
+        lines.push(format!(
+            "complete -c {} -n __fish_use_subcommand -a {} -d '{}'",
+            bin,
+            sub.name,
+            clean(&sub.about)
+        ));
+    }
+    let mut all = vec![];
+    flatten(subs, &[], &mut all);
+    for (path, sub) in all {
+        let condition = path
+            .iter()
+            .map(|name| format!("__fish_seen_subcommand_from {}", name))
+            .collect::<Vec<_>>()
+            .join("; and ");
+        for flag in &sub.flags {
+            lines.push(fish_flag(&condition, flag));
+        }
+        if !sub.subs.is_empty() {
+            let nested = sub.subs.iter().map(|s| s.name.as_str()).collect::<Vec<_>>().join(" ");
+            for nested_sub in &sub.subs {
+                lines.push(format!(
+                    "complete -c {} -n '{}; and not __fish_seen_subcommand_from {}' -a {} -d '{}'",
+                    bin,
+                    condition,
+                    nested,
+                    nested_sub.name,
+                    clean(&nested_sub.about)
+                ));
+            }
+        } else if let Some(p) = sub.positionals.first() {
+            if p.is_names() {
+                lines.push(format!(
+                    "complete -c {} -n '{}' -a '({} {} 2>/dev/null)'",
+                    bin, condition, bin, RECENT_COMMAND
+                ));
+            } else if !p.values.is_empty() {
+                lines.push(format!(
+                    "complete -c {} -n '{}' -a '{}'",
+                    bin,
+                    condition,
+                    p.values.join(" ")
+                ));
+            }
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::{generate, Shell, RECENT_COMMAND};
+    use crate::args::Cli;
+
+    #[test]
+    fn scripts_cover_the_cli() {
+        let recent = format!("watcher {}", RECENT_COMMAND);
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let script = generate(shell, Cli::command(), "watcher");
+            for word in ["summoner", "games", "match", "ddragon", "import", "region", "euw", "kr", &recent] {
+                assert!(script.contains(word), "{:?} script is missing {}", shell, word);
+            }
+            assert!(!script.contains("completions recent"), "{:?} offers the hidden command", shell);
+        }
+    }
+
+    #[test]
+    fn nested_subcommands() {
+        let bash = generate(Shell::Bash, Cli::command(), "watcher");
+        assert!(bash.contains("ddragon) COMPREPLY=($(compgen -W \"import\""));
+        assert!(bash.contains("|ddragon_import|") || bash.contains("|ddragon_import)"));
+        let zsh = generate(Shell::Zsh, Cli::command(), "watcher");
+        assert!(zsh.contains("_watcher_ddragon() {"));
+        assert!(zsh.contains("'import:"));
+        let fish = generate(Shell::Fish, Cli::command(), "watcher");
+        assert!(fish.contains(
+            "-n '__fish_seen_subcommand_from ddragon; and not __fish_seen_subcommand_from import' -a import"
+        ));
+    }
+}
@@ -0,0 +1,197 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use riven::consts::PlatformRoute;
+use serde::Deserialize;
+
+use crate::{api::cache::CacheConfig, logs::LogConfig, ui::keys::Keys, utils::route_by_name};
+
+pub const CONFIG_FILE: &str = "config.toml";
+pub const KEY_FILE: &str = "api_key";
+
+/// ~/.config/watcher/config.toml
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub api_key: Option<String>,
+    pub api_key_file: Option<PathBuf>,
+    /// one of ROUTE_NAMES, used when no --region is given
+    pub region: Option<String>,
+    pub match_concurrency: usize,
+    pub batch_concurrency: usize,
+    pub cache: CacheConfig,
+    pub log: LogConfig,
+    /// `main = "Faker#KR1 @ kr"`, used as `@main`
+    pub profiles: BTreeMap<String, String>,
+    /// `quit = ["q", "F10"]`, replaces the default keys of that TUI action
+    pub keys: BTreeMap<String, Vec<String>>,
+}
+
+/// a `[profiles]` entry, without a region the default one is used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub name: String,
+    pub riot_id: String,
+    pub route: Option<PlatformRoute>,
+}
+
+impl Profile {
+    pub fn parse(name: &str, value: &str) -> Result<Profile, String> {
+        let (riot_id, region) = match value.split_once('@') {
+            Some((riot_id, region)) => (riot_id.trim(), Some(region.trim())),
+            None => (value.trim(), None),
+        };
+        if riot_id.is_empty() {
+            return Err(format!("profile {} has no player", name));
+        }
+        let route = match region {
+            Some(region) => Some(
+                route_by_name(region)
+                    .ok_or_else(|| format!("profile {} has unknown region {}", name, region))?,
+            ),
+            None => None,
+        };
+        Ok(Profile {
+            name: name.to_string(),
+            riot_id: riot_id.to_string(),
+            route,
+        })
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            api_key: None,
+            api_key_file: None,
+            region: None,
+            match_concurrency: 5,
+            batch_concurrency: 5,
+            cache: CacheConfig::default(),
+            log: LogConfig::default(),
+            profiles: BTreeMap::new(),
+            keys: BTreeMap::new(),
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> Result<Config, String> {
+        let path = match config_path() {
+            Some(p) => p,
+            None => return Ok(Config::default()),
+        };
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+        let text = fs::read_to_string(&path)
+            .map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+        let config: Config = toml::from_str(&text)
+            .map_err(|e| format!("couldn't parse {}: {}", path.display(), e))?;
+        config
+            .check()
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(config)
+    }
+
+    /// bad regions, profiles and key bindings fail at startup instead of on first use
+    fn check(&self) -> Result<(), String> {
+        if let Some(region) = &self.region {
+            route_by_name(region).ok_or_else(|| format!("unknown region {}", region))?;
+        }
+        Keys::new(&self.keys)?;
+        self.profiles().map(|_| ())
+    }
+
+    pub fn default_route(&self) -> PlatformRoute {
+        self.region
+            .as_deref()
+            .and_then(route_by_name)
+            .unwrap_or(PlatformRoute::KR)
+    }
+
+    pub fn profiles(&self) -> Result<Vec<Profile>, String> {
+        self.profiles
+            .iter()
+            .map(|(name, value)| Profile::parse(name, value))
+            .collect()
+    }
+
+    /// `name` without the leading `@`
+    pub fn profile(&self, name: &str) -> Option<Profile> {
+        let value = self.profiles.get(name)?;
+        Profile::parse(name, value).ok()
+    }
+
+    pub fn key_file(&self) -> Option<PathBuf> {
+        match &self.api_key_file {
+            Some(p) => Some(expand_home(p)),
+            None => config_dir().map(|d| d.join(KEY_FILE)),
+        }
+    }
+}
+
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("watcher"))
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    config_dir().map(|d| d.join(CONFIG_FILE))
+}
+
+pub fn expand_home(path: &Path) -> PathBuf {
+    match path.strip_prefix("~") {
+        Ok(rest) => dirs::home_dir()
+            .map(|h| h.join(rest))
+            .unwrap_or_else(|| path.to_path_buf()),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use riven::consts::PlatformRoute;
+
+    use super::{Config, Profile};
+
+    #[test]
+    fn profile_with_region() {
+        let p = Profile::parse("main", "Faker#KR1 @ kr").unwrap();
+        assert_eq!(p.riot_id, "Faker#KR1");
+        assert_eq!(p.route, Some(PlatformRoute::KR));
+    }
+
+    #[test]
+    fn profile_without_region() {
+        let p = Profile::parse("alt", "Hide on bush").unwrap();
+        assert_eq!(p.riot_id, "Hide on bush");
+        assert_eq!(p.route, None);
+    }
+
+    #[test]
+    fn bad_profiles_fail() {
+        assert!(Profile::parse("main", "Faker#KR1 @ mars").is_err());
+        assert!(Profile::parse("main", " @ kr").is_err());
+    }
+
+    #[test]
+    fn config_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+region = "euw"
+
+[profiles]
+main = "Caps#EUW"
+kr = "Faker#KR1 @ kr"
+"#,
+        )
+        .unwrap();
+        assert!(config.check().is_ok());
+        assert_eq!(config.default_route(), PlatformRoute::EUW1);
+        assert_eq!(config.profile("kr").unwrap().route, Some(PlatformRoute::KR));
+        assert!(config.profile("missing").is_none());
+    }
+}
@@ -10,12 +10,25 @@ use ratatui::{
 use riven::{
     consts::{Team, Tier},
     models::{
-        champion_mastery_v4::ChampionMastery, league_v4::LeagueEntry, match_v5::Match,
+        champion_mastery_v4::ChampionMastery,
+        league_v4::LeagueEntry,
+        match_v5::{Match, Participant},
         summoner_v4::Summoner,
     },
 };
 
-use crate::ui::app::Window;
+use crate::{
+    api::{
+        player::Player,
+        spectator::LiveGame,
+        timeline::{EventKind, TimelineEvent},
+    },
+    static_data::{champion_name, item_name, map_name, rune_name, spell_name},
+    metrics::Metrics,
+    summary::{GameStat, Record, Summary},
+    ui::app::Window,
+    utils::{duration_secs, queue_name},
+};
 
 #[macro_export]
 macro_rules! no_data {
@@ -66,6 +79,41 @@ fn padding(text: String, padding: Pad, amount: usize, ch: u8) -> String {
     }
 }
 
+/// Riot ID of a match participant, summoner name for old games without one
+pub fn participant_name(p: &Participant) -> String {
+    if p.riot_id_name.is_empty() {
+        return p.summoner_name.clone();
+    }
+    format!("{}#{}", p.riot_id_name, p.riot_id_tagline)
+}
+
+/// keystone and secondary tree
+pub fn participant_runes(p: &Participant) -> (String, String) {
+    let keystone = p
+        .perks
+        .styles
+        .first()
+        .and_then(|s| s.selections.first())
+        .map(|s| rune_name(s.perk as i64))
+        .unwrap_or_default();
+    let secondary = p
+        .perks
+        .styles
+        .get(1)
+        .map(|s| rune_name(s.style as i64))
+        .unwrap_or_default();
+    (keystone, secondary)
+}
+
+/// item slots 0-6 without the empty ones, the trinket is last
+pub fn participant_items(p: &Participant) -> Vec<String> {
+    [p.item0, p.item1, p.item2, p.item3, p.item4, p.item5, p.item6]
+        .iter()
+        .map(|id| item_name(*id as i64))
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
 pub trait DisplayToText<T: Display>
 where
     Self: std::fmt::Display + Sized,
@@ -91,18 +139,21 @@ pub trait With {
 }
 
 #[derive(Clone)]
-pub struct SummonerDisplay(pub Summoner);
+pub struct SummonerDisplay(pub Player);
 
 impl Default for SummonerDisplay {
     fn default() -> Self {
-        SummonerDisplay(Summoner {
-            account_id: "".to_string(),
-            profile_icon_id: 23,
-            revision_date: 23,
-            name: "test".to_string(),
-            id: "lol".to_string(),
-            puuid: "24365".to_string(),
-            summoner_level: 69,
+        SummonerDisplay(Player {
+            summoner: Summoner {
+                account_id: "".to_string(),
+                profile_icon_id: 23,
+                revision_date: 23,
+                name: "test".to_string(),
+                id: "lol".to_string(),
+                puuid: "24365".to_string(),
+                summoner_level: 69,
+            },
+            riot_id: None,
         })
     }
 }
@@ -116,13 +167,12 @@ impl Display for SummonerDisplay {
 {}  {}:{}
                            "###,
             entry
-                .name
-                .clone()
+                .name()
                 .with(Color::Blue)
                 .attribute(crossterm::style::Attribute::Bold)
                 .attribute(crossterm::style::Attribute::Underlined),
             "lvl".with(Color::Reset),
-            entry.summoner_level.to_string().with(Color::Cyan)
+            entry.summoner.summoner_level.to_string().with(Color::Cyan)
         );
 
         write!(f, "{}", text)
@@ -130,7 +180,7 @@ impl Display for SummonerDisplay {
 }
 
 impl With for SummonerDisplay {
-    type Struct = Summoner;
+    type Struct = Player;
     fn with(entry: Self::Struct) -> SummonerDisplay {
         SummonerDisplay(entry)
     }
@@ -206,7 +256,7 @@ impl Display for ChampionMasteryDisplay {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let entry = &self.0;
 
-        let ch_id = format!("{}", entry.champion_id.name().unwrap_or("UNKNOWN").green());
+        let ch_id = format!("{}", champion_name(entry.champion_id).green());
         let ch_points = format!("{}", entry.champion_points.to_string().yellow().to_string()); //.with(Color::Yellow),
         let ch_level = format!(
             "{}",
@@ -276,7 +326,7 @@ impl Display for MatchDisplay {
                     let (_, r) = f;
                     let kda = format!("{}/{}/{}", r.kills, r.deaths, r.assists);
                     format!(
-                        "      {} {} {}  |  {} {}",
+                        "      {} {} {}  |  {} {} {}",
                         padding(
                             r.team_position.clone().with(Color::Cyan).to_string(),
                             Pad::Left,
@@ -284,7 +334,7 @@ impl Display for MatchDisplay {
                             b' '
                         ),
                         padding(
-                            r.summoner_name.clone().with(Color::Red).to_string(),
+                            participant_name(r).with(Color::Red).to_string(),
                             Pad::Left,
                             35,
                             b' '
@@ -296,15 +346,8 @@ impl Display for MatchDisplay {
                             b' '
                         ),
                         padding(kda.with(Color::Green).to_string(), Pad::Left, 20, b' '),
-                        padding(
-                            r.total_minions_killed
-                                .to_string()
-                                .with(Color::Cyan)
-                                .to_string(),
-                            Pad::Left,
-                            0,
-                            b' '
-                        ),
+                        padding(r.total_minions_killed.to_string(), Pad::Left, 5, b' ').with(Color::Cyan),
+                        metrics_text(&Metrics::new(&self.0, r)).with(Color::DarkGrey),
                     )
                 })
                 .collect::<Vec<String>>(),
@@ -325,7 +368,7 @@ impl Display for MatchDisplay {
                     let (_, b) = f;
                     let kda = format!("{}/{}/{}", b.kills, b.deaths, b.assists);
                     format!(
-                        "      {} {} {}  |  {} {}",
+                        "      {} {} {}  |  {} {} {}",
                         padding(
                             b.team_position.clone().with(Color::Cyan).to_string(),
                             Pad::Left,
@@ -333,7 +376,7 @@ impl Display for MatchDisplay {
                             b' '
                         ),
                         padding(
-                            b.summoner_name.clone().with(Color::Red).to_string(),
+                            participant_name(b).with(Color::Red).to_string(),
                             Pad::Left,
                             35,
                             b' '
@@ -345,15 +388,8 @@ impl Display for MatchDisplay {
                             b' '
                         ),
                         padding(kda.with(Color::Green).to_string(), Pad::Left, 20, b' '),
-                        padding(
-                            b.total_minions_killed
-                                .to_string()
-                                .with(Color::Cyan)
-                                .to_string(),
-                            Pad::Left,
-                            0,
-                            b' '
-                        ),
+                        padding(b.total_minions_killed.to_string(), Pad::Left, 5, b' ').with(Color::Cyan),
+                        metrics_text(&Metrics::new(&self.0, b)).with(Color::DarkGrey),
                     )
                 })
                 .collect::<Vec<String>>(),
@@ -371,32 +407,370 @@ impl With for MatchDisplay {
 }
 
 impl MatchDisplay {
-    #[allow(dead_code)]
-    pub fn list(&mut self, name: String) -> Vec<Line> {
-        let text = self
-            .0
-            .info
-            .participants
-            .iter()
-            .map(|f| {
-                if f.summoner_name == name && f.win {
-                    Span::styled(
-                        "won",
-                        Style::default()
-                            .fg(style::Color::Green)
-                            .add_modifier(Modifier::BOLD),
-                    )
+    /// row of the games list for `puuid`, won or lost with cs/min and kill participation
+    pub fn list(&self, puuid: &str) -> Line<'static> {
+        let p = match self.0.info.participants.iter().find(|p| p.puuid == puuid) {
+            Some(p) => p,
+            None => return Line::from(Span::styled("no_data", Style::default().fg(style::Color::Red))),
+        };
+        let result = match p.win {
+            true => Span::styled("win ", Style::default().fg(style::Color::Green).add_modifier(Modifier::BOLD)),
+            false => Span::styled("lose", Style::default().fg(style::Color::Red).add_modifier(Modifier::BOLD)),
+        };
+        let m = Metrics::new(&self.0, p);
+        Line::from(vec![
+            result,
+            Span::styled(
+                format!(" {:.1}cs/m {:.0}%kp", m.cs_per_min, m.kill_participation * 100.0),
+                Style::default().fg(style::Color::DarkGray),
+            ),
+        ])
+    }
+}
+
+/// the expanded scoreboard, two lines per participant and team totals
+#[derive(Clone)]
+pub struct ScoreboardDisplay(pub Match);
+
+impl DisplayToText<ScoreboardDisplay> for ScoreboardDisplay {}
+
+impl Display for ScoreboardDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines: Vec<String> = Vec::default();
+        let entry = &self.0.info;
+
+        let duration = duration_secs(entry);
+        lines.push(format!(
+            "{}  {}  {}  {}",
+            entry
+                .game_mode
+                .to_string()
+                .with(Color::Reset)
+                .attribute(crossterm::style::Attribute::Bold)
+                .attribute(crossterm::style::Attribute::Underlined),
+            map_name(entry.map_id.0 as i64).with(Color::Grey),
+            queue_name(entry.queue_id.0).with(Color::Grey),
+            format!("{}:{:02}", duration / 60, duration % 60).with(Color::Cyan),
+        ));
+
+        for (team, name, color) in [(Team::BLUE, "Team Blue", Color::Blue), (Team::RED, "Team Red", Color::Red)] {
+            let players = entry
+                .participants
+                .iter()
+                .filter(|p| p.team_id == team)
+                .collect::<Vec<_>>();
+            let sum = |stat: fn(&Participant) -> i32| players.iter().map(|p| stat(p)).sum::<i32>();
+            let result = match players.first().map(|p| p.win) {
+                Some(true) => "WIN".with(Color::Green),
+                Some(false) => "LOSE".with(Color::Red),
+                None => "".with(Color::Reset),
+            };
+            let mut totals = format!(
+                "kills {}  gold {}  damage {}  vision {}",
+                sum(|p| p.kills),
+                thousands(sum(|p| p.gold_earned)),
+                thousands(sum(|p| p.total_damage_dealt_to_champions)),
+                sum(|p| p.vision_score),
+            );
+            if let Some(objectives) = entry.teams.iter().find(|t| t.team_id == team).map(|t| &t.objectives) {
+                totals += &format!(
+                    "  towers {}  dragons {}  barons {}",
+                    objectives.tower.kills, objectives.dragon.kills, objectives.baron.kills
+                );
+            }
+            lines.push(format!(
+                "{}  {}  {}",
+                name.with(color)
+                    .attribute(crossterm::style::Attribute::Bold)
+                    .attribute(crossterm::style::Attribute::Underlined),
+                result,
+                totals.with(Color::Grey),
+            ));
+
+            for p in players {
+                let multikill = if p.penta_kills > 0 {
+                    "PENTA"
+                } else if p.quadra_kills > 0 {
+                    "QUADRA"
+                } else if p.triple_kills > 0 {
+                    "TRIPLE"
+                } else if p.double_kills > 0 {
+                    "DOUBLE"
                 } else {
-                    Span::styled(
-                        "lose",
-                        Style::default()
-                            .fg(style::Color::Green)
-                            .add_modifier(Modifier::BOLD),
+                    ""
+                };
+                lines.push(format!(
+                    "      {} {} {} {} {} {} {} {} {} {}",
+                    padding(p.team_position.clone(), Pad::Left, 8, b' ').with(Color::Cyan),
+                    padding(participant_name(p), Pad::Left, 25, b' ').with(color),
+                    padding(format!("{} {}", p.champion_name, p.champ_level), Pad::Left, 16, b' ').with(Color::Yellow),
+                    padding(format!("{}/{}/{}", p.kills, p.deaths, p.assists), Pad::Left, 9, b' ').with(Color::Green),
+                    padding(format!("{}cs", p.total_minions_killed + p.neutral_minions_killed), Pad::Left, 6, b' ').with(Color::Cyan),
+                    padding(format!("{} gold", thousands(p.gold_earned)), Pad::Left, 11, b' ').with(Color::DarkYellow),
+                    padding(format!("{} dmg", thousands(p.total_damage_dealt_to_champions)), Pad::Left, 10, b' ').with(Color::Red),
+                    padding(format!("{} taken", thousands(p.total_damage_taken)), Pad::Left, 12, b' ').with(Color::DarkGrey),
+                    padding(format!("vis {} wards {}/{}", p.vision_score, p.wards_placed, p.wards_killed), Pad::Left, 20, b' ').with(Color::Magenta),
+                    multikill.with(Color::Yellow).attribute(crossterm::style::Attribute::Bold),
+                ));
+
+                let (keystone, secondary) = participant_runes(p);
+                lines.push(format!(
+                    "      {} {} {} {}",
+                    padding(String::new(), Pad::Left, 8, b' '),
+                    padding(
+                        format!("{}/{}", spell_name(p.summoner1_id as i64), spell_name(p.summoner2_id as i64)),
+                        Pad::Left,
+                        25,
+                        b' '
                     )
-                }
+                    .with(Color::Cyan),
+                    padding(format!("{} / {}", keystone, secondary), Pad::Left, 30, b' ').with(Color::Magenta),
+                    participant_items(p).join(", ").with(Color::Grey),
+                ));
+            }
+        }
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl With for ScoreboardDisplay {
+    type Struct = Match;
+    fn with(entry: Match) -> ScoreboardDisplay {
+        ScoreboardDisplay(entry)
+    }
+}
+
+#[derive(Clone)]
+pub struct SummaryDisplay(pub Summary);
+
+impl DisplayToText<SummaryDisplay> for SummaryDisplay {}
+
+/// `12W 8L  60%  3.1 KDA`
+fn record_text(r: &Record) -> String {
+    let rate = format!("{:.0}%", r.win_rate() * 100.0);
+    format!(
+        "{} {}  {}  {}",
+        format!("{}W", r.wins).with(Color::Green),
+        format!("{}L", r.losses()).with(Color::Red),
+        padding(rate, Pad::Right, 4, b' ').with(if r.win_rate() >= 0.5 { Color::Green } else { Color::Red }),
+        format!("{:.2} KDA", r.kda()).with(Color::Cyan),
+    )
+}
+
+fn game_text(g: &GameStat) -> String {
+    format!(
+        "{} {} {}  {}",
+        padding(g.champion.clone(), Pad::Left, 14, b' ').with(Color::Yellow),
+        padding(format!("{}/{}/{}", g.kills, g.deaths, g.assists), Pad::Left, 9, b' ').with(Color::Green),
+        match g.win {
+            true => "win ".with(Color::Green),
+            false => "lose".with(Color::Red),
+        },
+        g.match_id.clone().with(Color::DarkGrey),
+    )
+}
+
+impl Display for SummaryDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let summary = &self.0;
+        let title = |t: &str| {
+            t.to_string()
+                .with(Color::Reset)
+                .attribute(crossterm::style::Attribute::Bold)
+                .attribute(crossterm::style::Attribute::Underlined)
+                .to_string()
+        };
+        let mut lines: Vec<String> = Vec::default();
+
+        let streak = match summary.streak {
+            0 => "".to_string(),
+            n if n > 0 => format!("{} {}", "streak".with(Color::Grey), format!("{}W", n).with(Color::Green)),
+            n => format!("{} {}", "streak".with(Color::Grey), format!("{}L", -n).with(Color::Red)),
+        };
+        lines.push(format!(
+            "{}  {}  {}  {}",
+            title(&format!("{} games", summary.overall.games)),
+            record_text(&summary.overall),
+            format!(
+                "{:.1}/{:.1}/{:.1}",
+                summary.overall.kills as f64 / summary.overall.games.max(1) as f64,
+                summary.overall.deaths as f64 / summary.overall.games.max(1) as f64,
+                summary.overall.assists as f64 / summary.overall.games.max(1) as f64,
+            )
+            .with(Color::Grey),
+            streak,
+        ));
+
+        for (name, records) in [("Champions", &summary.champions), ("Roles", &summary.roles)] {
+            lines.push(title(name));
+            for (key, record) in records {
+                lines.push(format!(
+                    "      {} {}",
+                    padding(key.clone(), Pad::Left, 14, b' ').with(Color::Yellow),
+                    record_text(record)
+                ));
+            }
+        }
+
+        if let (Some(best), Some(worst)) = (&summary.best, &summary.worst) {
+            lines.push(title("Best / worst"));
+            lines.push(format!("      {}", game_text(best)));
+            lines.push(format!("      {}", game_text(worst)));
+        }
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl With for SummaryDisplay {
+    type Struct = Summary;
+    fn with(entry: Summary) -> SummaryDisplay {
+        SummaryDisplay(entry)
+    }
+}
+
+/// `7.2cs/m 64%kp 28%dmg 410g/m 1.1vis/m 1.9dmg/g 20%deaths`
+pub fn metrics_text(m: &Metrics) -> String {
+    format!(
+        "{:.1}cs/m {:.0}%kp {:.0}%dmg {:.0}g/m {:.1}vis/m {:.1}dmg/g {:.0}%deaths",
+        m.cs_per_min,
+        m.kill_participation * 100.0,
+        m.damage_share * 100.0,
+        m.gold_per_min,
+        m.vision_per_min,
+        m.damage_per_gold,
+        m.death_share * 100.0
+    )
+}
+
+/// 12400 -> 12.4k
+fn thousands(n: i32) -> String {
+    if n.abs() < 1000 {
+        return n.to_string();
+    }
+    format!("{:.1}k", n as f64 / 1000.0)
+}
+
+#[derive(Clone)]
+pub struct LiveGameDisplay(pub LiveGame);
+
+impl DisplayToText<LiveGameDisplay> for LiveGameDisplay {}
+
+impl Display for LiveGameDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let live = &self.0;
+        let game = &live.game;
+        let mut lines: Vec<String> = Vec::default();
+
+        let elapsed = game.elapsed();
+        lines.push(format!(
+            "{}  {}  {}  {}",
+            game.game_mode
+                .clone()
+                .with(Color::Reset)
+                .attribute(crossterm::style::Attribute::Bold)
+                .attribute(crossterm::style::Attribute::Underlined),
+            map_name(game.map_id).with(Color::Grey),
+            game.game_queue_config_id
+                .map(|q| queue_name(q.0))
+                .unwrap_or_default()
+                .with(Color::Grey),
+            format!("{}:{:02}", elapsed / 60, elapsed % 60).with(Color::Cyan),
+        ));
+
+        for (team, name, color) in [(Team::BLUE, "Team Blue", Color::Blue), (Team::RED, "Team Red", Color::Red)] {
+            let bans = game
+                .banned_champions
+                .iter()
+                .filter(|b| b.team_id == team && b.champion_id.0 >= 0)
+                .map(|b| champion_name(b.champion_id))
+                .collect::<Vec<_>>()
+                .join(", ");
+            lines.push(format!(
+                "{}  {} {}",
+                name.with(color)
+                    .attribute(crossterm::style::Attribute::Bold)
+                    .attribute(crossterm::style::Attribute::Underlined),
+                "bans:".with(Color::Grey),
+                bans.with(Color::DarkGrey),
+            ));
+            for (p, rank) in game.participants.iter().zip(&live.ranks).filter(|(p, _)| p.team_id == team) {
+                let runes = match &p.perks {
+                    Some(perks) => format!(
+                        "{} / {}",
+                        perks.perk_ids.first().map(|id| rune_name(*id)).unwrap_or_default(),
+                        rune_name(perks.perk_sub_style)
+                    ),
+                    None => "".to_string(),
+                };
+                let rank = match rank {
+                    Some(r) => format!(
+                        "{} {} {}LP",
+                        r.tier.map(|t| t.to_string()).unwrap_or_default(),
+                        r.rank.map(|d| d.to_string()).unwrap_or_default(),
+                        r.league_points
+                    ),
+                    None => "unranked".to_string(),
+                };
+                lines.push(format!(
+                    "      {} {} {} {} {}",
+                    padding(p.riot_id.clone().unwrap_or_default(), Pad::Left, 30, b' ').with(color),
+                    padding(champion_name(p.champion_id), Pad::Left, 16, b' ').with(Color::Yellow),
+                    padding(format!("{}/{}", spell_name(p.spell1_id), spell_name(p.spell2_id)), Pad::Left, 18, b' ').with(Color::Cyan),
+                    padding(runes, Pad::Left, 30, b' ').with(Color::Magenta),
+                    rank.with(Color::Green),
+                ));
+            }
+        }
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl With for LiveGameDisplay {
+    type Struct = LiveGame;
+    fn with(entry: LiveGame) -> LiveGameDisplay {
+        LiveGameDisplay(entry)
+    }
+}
+
+/// one line per event, `12:34 blue baron`
+#[derive(Clone)]
+pub struct TimelineEventsDisplay(pub Vec<TimelineEvent>);
+
+impl DisplayToText<TimelineEventsDisplay> for TimelineEventsDisplay {}
+
+impl Display for TimelineEventsDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines = self
+            .0
+            .iter()
+            .map(|e| {
+                let secs = e.timestamp / 1000;
+                let team = match e.team {
+                    Team::BLUE => "blue".with(Color::Blue),
+                    Team::RED => "red ".with(Color::Red),
+                    _ => "    ".with(Color::Reset),
+                };
+                let text = match e.kind {
+                    EventKind::Kill => e.text.clone().with(Color::Reset),
+                    EventKind::Dragon => e.text.clone().with(Color::Magenta),
+                    EventKind::Baron => e.text.clone().with(Color::DarkMagenta),
+                    EventKind::Herald | EventKind::Monster => e.text.clone().with(Color::Cyan),
+                    EventKind::Tower | EventKind::Inhibitor => e.text.clone().with(Color::Yellow),
+                };
+                format!("{} {} {}", format!("{:>2}:{:02}", secs / 60, secs % 60).with(Color::Grey), team, text)
             })
-            .collect::<Vec<Span>>();
-        vec![Line::from(text)]
+            .collect::<Vec<_>>();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl With for TimelineEventsDisplay {
+    type Struct = Vec<TimelineEvent>;
+    fn with(entry: Vec<TimelineEvent>) -> TimelineEventsDisplay {
+        TimelineEventsDisplay(entry)
     }
 }
 
@@ -0,0 +1,92 @@
+use std::fmt::Display;
+
+use riven::{reqwest::StatusCode, RiotApiError};
+
+#[derive(Debug, Clone)]
+pub enum WatcherError {
+    /// summoner, account or match that doesn't exist
+    NotFound(String),
+    /// retry after this many seconds when Riot said so
+    RateLimited(Option<u64>),
+    /// 401/403, almost always an expired development key
+    Forbidden,
+    Network(String),
+    BadIndex { index: usize, len: usize },
+    Riot(String),
+    /// a local file that couldn't be read or parsed, nothing to do with the api
+    Io(String),
+}
+
+impl WatcherError {
+    /// exit code of the CLI when this was the first failure
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            WatcherError::Riot(_) => 1,
+            WatcherError::NotFound(_) => 2,
+            WatcherError::BadIndex { .. } => 3,
+            WatcherError::Forbidden => 4,
+            WatcherError::RateLimited(_) => 5,
+            WatcherError::Network(_) => 6,
+            WatcherError::Io(_) => 7,
+        }
+    }
+
+    /// every exit code with what it means, for `--help`
+    pub fn exit_codes() -> Vec<(i32, &'static str)> {
+        [
+            (WatcherError::Riot(String::new()), "riot api error"),
+            (WatcherError::NotFound(String::new()), "not found"),
+            (WatcherError::BadIndex { index: 0, len: 0 }, "bad game index"),
+            (WatcherError::Forbidden, "key rejected/expired"),
+            (WatcherError::RateLimited(None), "rate limited"),
+            (WatcherError::Network(String::new()), "network error"),
+            (WatcherError::Io(String::new()), "local file unreadable"),
+        ]
+        .into_iter()
+        .map(|(err, what)| (err.exit_code(), what))
+        .collect()
+    }
+}
+
+impl From<RiotApiError> for WatcherError {
+    fn from(err: RiotApiError) -> Self {
+        match err.status_code() {
+            Some(StatusCode::NOT_FOUND) => WatcherError::NotFound(
+                err.response()
+                    .map(|r| r.url().path().to_string())
+                    .unwrap_or_default(),
+            ),
+            Some(StatusCode::UNAUTHORIZED) | Some(StatusCode::FORBIDDEN) => WatcherError::Forbidden,
+            Some(StatusCode::TOO_MANY_REQUESTS) => WatcherError::RateLimited(
+                err.response()
+                    .and_then(|r| r.headers().get("retry-after"))
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse::<u64>().ok()),
+            ),
+            Some(_) => WatcherError::Riot(err.to_string()),
+            None => WatcherError::Network(err.source_reqwest_error().to_string()),
+        }
+    }
+}
+
+impl Display for WatcherError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WatcherError::NotFound(what) => write!(f, "not found: {}", what),
+            WatcherError::RateLimited(Some(secs)) => {
+                write!(f, "rate limited, retry after {}s", secs)
+            }
+            WatcherError::RateLimited(None) => write!(f, "rate limited"),
+            WatcherError::Forbidden => write!(
+                f,
+                "API key was rejected, development keys expire after 24h, regenerate it at https://developer.riotgames.com"
+            ),
+            WatcherError::Network(e) => write!(f, "network error: {}", e),
+            WatcherError::BadIndex { index, len } => {
+                write!(f, "no game {}, only {} games were fetched", index, len)
+            }
+            WatcherError::Riot(e) => write!(f, "riot api error: {}", e),
+            WatcherError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
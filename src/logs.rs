@@ -0,0 +1,168 @@
+//! everything logged this session, kept for the TUI log view and optionally written to a file
+
+use std::{
+    collections::VecDeque,
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    sync::Mutex,
+};
+
+use serde::Deserialize;
+
+use crate::{
+    config::expand_home,
+    utils::{Log, LogKind},
+};
+
+/// `[log]` in config.toml, no file unless `file` is set
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct LogConfig {
+    /// entries kept in memory
+    pub history: usize,
+    pub file: Option<PathBuf>,
+    /// bytes before the file is rotated to `file.1`
+    pub max_size: u64,
+    /// rotated files kept next to it
+    pub keep: usize,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        LogConfig {
+            history: 1000,
+            file: None,
+            max_size: 1024 * 1024,
+            keep: 3,
+        }
+    }
+}
+
+/// bounded, the oldest entry goes first
+#[derive(Debug, Clone)]
+pub struct History {
+    entries: VecDeque<Log>,
+    capacity: usize,
+}
+
+impl History {
+    pub const fn new(capacity: usize) -> History {
+        History {
+            entries: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, log: Log) {
+        while self.entries.len() >= self.capacity.max(1) {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(log);
+    }
+
+    /// oldest first, only `level` and above
+    pub fn filtered(&self, level: &LogKind) -> Vec<Log> {
+        self.entries
+            .iter()
+            .filter(|l| l.kind >= *level)
+            .cloned()
+            .collect()
+    }
+}
+
+struct LogFile {
+    path: PathBuf,
+    file: File,
+    max_size: u64,
+    keep: usize,
+}
+
+impl LogFile {
+    fn open(path: PathBuf, max_size: u64, keep: usize) -> Option<LogFile> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).ok()?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path).ok()?;
+        Some(LogFile { path, file, max_size, keep })
+    }
+
+    fn write(&mut self, log: &Log) {
+        let size = self.file.metadata().map(|m| m.len()).unwrap_or(0);
+        if size >= self.max_size {
+            self.rotate();
+        }
+        let _ = writeln!(self.file, "{} {:?} {}", log.time_stamp, log.kind, log.msg);
+    }
+
+    /// watcher.log -> watcher.log.1 -> watcher.log.2, the last one is dropped
+    fn rotate(&mut self) {
+        let rotated = |n: usize| PathBuf::from(format!("{}.{}", self.path.display(), n));
+        for n in (1..self.keep).rev() {
+            let _ = fs::rename(rotated(n), rotated(n + 1));
+        }
+        if self.keep == 0 {
+            let _ = fs::remove_file(&self.path);
+        } else {
+            let _ = fs::rename(&self.path, rotated(1));
+        }
+        if let Ok(file) = File::create(&self.path) {
+            self.file = file;
+        }
+    }
+}
+
+static HISTORY: Mutex<History> = Mutex::new(History::new(1000));
+static FILE: Mutex<Option<LogFile>> = Mutex::new(None);
+
+/// before anything is logged, otherwise the defaults are used
+pub fn init(config: &LogConfig) {
+    if let Ok(mut history) = HISTORY.lock() {
+        history.capacity = config.history;
+    }
+    if let (Some(path), Ok(mut file)) = (&config.file, FILE.lock()) {
+        *file = LogFile::open(expand_home(path), config.max_size, config.keep);
+    }
+}
+
+/// adds a new entry to the history and the file, returns it for the log panel
+pub fn record(kind: LogKind, msg: String) -> Log {
+    let log = Log::new(kind, msg);
+    if let Ok(mut history) = HISTORY.lock() {
+        history.push(log.clone());
+    }
+    if let Ok(mut file) = FILE.lock() {
+        if let Some(file) = file.as_mut() {
+            file.write(&log);
+        }
+    }
+    log
+}
+
+pub fn entries(level: &LogKind) -> Vec<Log> {
+    HISTORY
+        .lock()
+        .map(|history| history.filtered(level))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::History;
+    use crate::utils::{Log, LogKind};
+
+    #[test]
+    fn history_is_bounded_and_filtered() {
+        let mut history = History::new(3);
+        for (i, kind) in [LogKind::Debug, LogKind::Info, LogKind::Warning, LogKind::Error]
+            .into_iter()
+            .enumerate()
+        {
+            history.push(Log::new(kind, i.to_string()));
+        }
+        let all = history.filtered(&LogKind::Debug);
+        assert_eq!(all.iter().map(|l| l.msg.as_str()).collect::<Vec<_>>(), ["1", "2", "3"]);
+        assert_eq!(history.filtered(&LogKind::Warning).len(), 2);
+        assert_eq!(history.filtered(&LogKind::Error)[0].msg, "3");
+    }
+}
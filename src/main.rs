@@ -1,45 +1,83 @@
-use std::env::args;
+use std::sync::Arc;
 
-use args::Args;
+use api::{credentials::ApiKey, client::WatcherClient};
+use args::{run_offline, Cli, Command};
+use clap::Parser;
+use config::Config;
 use ui::ui::ui;
-use utils::print_help ;
 
 mod api;
+mod config;
+mod error;
+mod logs;
+mod output;
 mod display;
+mod metrics;
 mod ui;
 mod utils;
 mod args;
+mod completions;
+mod recent;
+mod roster;
+mod static_data;
+mod summary;
+
+/// clap's own usage errors exit with 2, which is taken by "not found"
+const USAGE_EXIT: i32 = 64;
+/// bad config.toml or no API key, nothing ran
+const CONFIG_EXIT: i32 = 78;
 
 #[tokio::main]
 async fn main() -> Result<(), ()> {
-    let args: Vec<String> = args().collect();
-
-    let api_key: &str; 
-    match option_env!("RGAPI_KEY") {
-        None => {
-            println!("RGAPI_KEY is empty check if its exported");
-            return Ok(());
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(err) => {
+            let _ = err.print();
+            std::process::exit(if err.use_stderr() { USAGE_EXIT } else { 0 });
         }
-        Some(key) => api_key = key,
+    };
+
+    if run_offline(&cli.command) {
+        return Ok(());
     }
 
-    if args.len() == 1 {
-        if let Err(err) = ui(api_key).await {
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("ERR: {}", err);
+            std::process::exit(CONFIG_EXIT);
+        }
+    };
+    logs::init(&config.log);
+
+    let api_key = match ApiKey::resolve(cli.api_key.clone(), &config) {
+        Ok(key) => key,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(CONFIG_EXIT);
+        }
+    };
+
+    let client = Arc::new(WatcherClient::new(api_key, &config, cli.cache_mode()));
+
+    if matches!(cli.command, None | Some(Command::Tui)) {
+        if let Err(err) = ui(client, &config).await {
             println!("ERR: {}", err)
         }
         return Ok(());
     };
 
-
-    if args.len() == 2 {
-        if args[1] == "-h" || args[1] == "--help" {
-            print_help();
-            return Ok(());
+    eprintln!("{}", client.api_key);
+    let failed = match cli.execute(&client, &config).await {
+        Ok(failed) => failed,
+        Err(err) => {
+            eprintln!("ERR: {}", err);
+            std::process::exit(err.exit_code());
         }
+    };
+    if let Some((_, err)) = failed.first() {
+        std::process::exit(err.exit_code());
     }
 
-    let a = Args::new(args[1..].to_vec());
-    let _ = a.execute(api_key).await;
-        
     Ok(())
 }
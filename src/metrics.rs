@@ -0,0 +1,110 @@
+//! per-game numbers that can be compared across games of different lengths
+
+use riven::models::match_v5::{Match, Participant};
+use serde::Serialize;
+
+use crate::utils::duration_secs;
+
+/// shares and participation are 0..1
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Metrics {
+    pub cs_per_min: f64,
+    pub kill_participation: f64,
+    pub damage_share: f64,
+    pub gold_per_min: f64,
+    pub vision_per_min: f64,
+    pub damage_per_gold: f64,
+    pub death_share: f64,
+}
+
+/// what the metrics are computed from, for one player or a whole team
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Totals {
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    pub cs: i32,
+    pub gold: i32,
+    pub damage: i32,
+    pub vision: i32,
+}
+
+impl Totals {
+    pub fn of(p: &Participant) -> Totals {
+        Totals {
+            kills: p.kills,
+            deaths: p.deaths,
+            assists: p.assists,
+            cs: p.total_minions_killed + p.neutral_minions_killed,
+            gold: p.gold_earned,
+            damage: p.total_damage_dealt_to_champions,
+            vision: p.vision_score,
+        }
+    }
+
+    fn add(self, other: Totals) -> Totals {
+        Totals {
+            kills: self.kills + other.kills,
+            deaths: self.deaths + other.deaths,
+            assists: self.assists + other.assists,
+            cs: self.cs + other.cs,
+            gold: self.gold + other.gold,
+            damage: self.damage + other.damage,
+            vision: self.vision + other.vision,
+        }
+    }
+}
+
+impl Metrics {
+    /// `p` against the rest of its team in `game`
+    pub fn new(game: &Match, p: &Participant) -> Metrics {
+        let team = game
+            .info
+            .participants
+            .iter()
+            .filter(|other| other.team_id == p.team_id)
+            .map(Totals::of)
+            .fold(Totals::default(), Totals::add);
+        let minutes = duration_secs(&game.info) as f64 / 60.0;
+        Metrics::compute(Totals::of(p), team, minutes)
+    }
+
+    /// anything divided by zero is 0
+    pub fn compute(p: Totals, team: Totals, minutes: f64) -> Metrics {
+        let ratio = |a: i32, b: f64| if b > 0.0 { a as f64 / b } else { 0.0 };
+        Metrics {
+            cs_per_min: ratio(p.cs, minutes),
+            kill_participation: ratio(p.kills + p.assists, team.kills as f64),
+            damage_share: ratio(p.damage, team.damage as f64),
+            gold_per_min: ratio(p.gold, minutes),
+            vision_per_min: ratio(p.vision, minutes),
+            damage_per_gold: ratio(p.damage, p.gold as f64),
+            death_share: ratio(p.deaths, team.deaths as f64),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Metrics, Totals};
+
+    #[test]
+    fn metrics_from_totals() {
+        let p = Totals { kills: 5, deaths: 2, assists: 7, cs: 240, gold: 12000, damage: 24000, vision: 30 };
+        let team = Totals { kills: 20, deaths: 10, assists: 40, cs: 800, gold: 60000, damage: 80000, vision: 120 };
+        let m = Metrics::compute(p, team, 30.0);
+        assert_eq!(m.cs_per_min, 8.0);
+        assert_eq!(m.kill_participation, 0.6);
+        assert_eq!(m.damage_share, 0.3);
+        assert_eq!(m.gold_per_min, 400.0);
+        assert_eq!(m.vision_per_min, 1.0);
+        assert_eq!(m.damage_per_gold, 2.0);
+        assert_eq!(m.death_share, 0.2);
+    }
+
+    #[test]
+    fn no_division_by_zero() {
+        let m = Metrics::compute(Totals::default(), Totals::default(), 0.0);
+        assert_eq!(m, Metrics::default());
+    }
+}
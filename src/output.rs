@@ -0,0 +1,694 @@
+use std::{
+    fmt::Display,
+    io::{self, Write},
+};
+
+use riven::{
+    consts::Team,
+    models::{
+        champion_mastery_v4::ChampionMastery,
+        league_v4::LeagueEntry,
+        match_v5::{Match, Participant},
+    },
+};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    api::{player::Player, spectator::LiveGame},
+    display::{participant_items, participant_name, participant_runes},
+    static_data::{champion_name, map_name, rune_name, spell_name},
+    error::WatcherError,
+    metrics::Metrics,
+    summary::{self, GameStat, Summary},
+    utils::{duration_secs, format_date, queue_name},
+};
+
+/// bumped whenever a field is renamed or removed, new fields don't bump it
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// colored text for humans
+    Text,
+    /// one document `{"schema_version", "results": [..]}` printed at the end
+    Json,
+    /// one record per line as soon as it's ready
+    Ndjson,
+    /// flat rows, a header is printed whenever the record kind changes
+    Csv,
+    /// same as csv, tab separated
+    Tsv,
+}
+
+/// how a record is flattened for csv/tsv, `player` and `region` columns are added in front
+pub trait Table {
+    const HEADER: &'static [&'static str];
+    fn rows(&self) -> Vec<Vec<String>>;
+}
+
+/// `{"schema_version": 1, "kind": "rank", "player": "Name#TAG", "region": "KR", "data": ..}`
+#[derive(Serialize)]
+pub struct Record<T: Serialize> {
+    pub schema_version: u32,
+    pub kind: &'static str,
+    pub player: String,
+    pub region: String,
+    pub data: T,
+}
+
+#[derive(Serialize)]
+pub struct SummonerJson {
+    pub riot_id: Option<String>,
+    pub name: String,
+    pub puuid: String,
+    pub summoner_id: String,
+    pub level: i64,
+    pub profile_icon_id: i32,
+}
+
+impl Table for SummonerJson {
+    const HEADER: &'static [&'static str] = &["riot_id", "name", "puuid", "summoner_id", "level"];
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![vec![
+            self.riot_id.clone().unwrap_or_default(),
+            self.name.clone(),
+            self.puuid.clone(),
+            self.summoner_id.clone(),
+            self.level.to_string(),
+        ]]
+    }
+}
+
+impl From<&Player> for SummonerJson {
+    fn from(p: &Player) -> Self {
+        SummonerJson {
+            riot_id: p.riot_id.as_ref().map(|id| id.to_string()),
+            name: p.summoner.name.clone(),
+            puuid: p.summoner.puuid.clone(),
+            summoner_id: p.summoner.id.clone(),
+            level: p.summoner.summoner_level,
+            profile_icon_id: p.summoner.profile_icon_id,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct RankJson {
+    pub queue: String,
+    pub tier: Option<String>,
+    pub division: Option<String>,
+    pub league_points: i32,
+    pub wins: i32,
+    pub losses: i32,
+    pub hot_streak: bool,
+}
+
+impl Table for Vec<RankJson> {
+    const HEADER: &'static [&'static str] = &["queue", "tier", "division", "league_points", "wins", "losses", "hot_streak"];
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.iter()
+            .map(|r| {
+                vec![
+                    r.queue.clone(),
+                    r.tier.clone().unwrap_or_default(),
+                    r.division.clone().unwrap_or_default(),
+                    r.league_points.to_string(),
+                    r.wins.to_string(),
+                    r.losses.to_string(),
+                    r.hot_streak.to_string(),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl From<&LeagueEntry> for RankJson {
+    fn from(e: &LeagueEntry) -> Self {
+        RankJson {
+            queue: e.queue_type.to_string(),
+            tier: e.tier.map(|t| t.to_string()),
+            division: e.rank.map(|d| d.to_string()),
+            league_points: e.league_points,
+            wins: e.wins,
+            losses: e.losses,
+            hot_streak: e.hot_streak,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct MasteryJson {
+    pub champion_id: i16,
+    pub champion: Option<String>,
+    pub level: i32,
+    pub points: i32,
+    pub last_play_time: i64,
+}
+
+impl Table for Vec<MasteryJson> {
+    const HEADER: &'static [&'static str] = &["champion_id", "champion", "level", "points", "last_play_time"];
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.iter()
+            .map(|m| {
+                vec![
+                    m.champion_id.to_string(),
+                    m.champion.clone().unwrap_or_default(),
+                    m.level.to_string(),
+                    m.points.to_string(),
+                    m.last_play_time.to_string(),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl From<&ChampionMastery> for MasteryJson {
+    fn from(m: &ChampionMastery) -> Self {
+        MasteryJson {
+            champion_id: m.champion_id.0,
+            champion: Some(champion_name(m.champion_id)),
+            level: m.champion_level,
+            points: m.champion_points,
+            last_play_time: m.last_play_time,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ParticipantJson {
+    pub riot_id: String,
+    pub puuid: String,
+    pub team: &'static str,
+    pub position: String,
+    pub champion: String,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    pub cs: i32,
+    pub gold: i32,
+    pub damage: i32,
+    pub vision: i32,
+    pub win: bool,
+    pub level: i32,
+    pub damage_taken: i32,
+    pub wards_placed: i32,
+    pub wards_killed: i32,
+    pub double_kills: i32,
+    pub triple_kills: i32,
+    pub quadra_kills: i32,
+    pub penta_kills: i32,
+    pub spells: [String; 2],
+    pub keystone: String,
+    pub secondary: String,
+    pub items: Vec<String>,
+    pub metrics: Metrics,
+}
+
+impl ParticipantJson {
+    pub fn new(m: &Match, p: &Participant) -> ParticipantJson {
+        let (keystone, secondary) = participant_runes(p);
+        ParticipantJson {
+            riot_id: participant_name(p),
+            puuid: p.puuid.clone(),
+            team: team_name(p.team_id),
+            position: p.team_position.clone(),
+            champion: p.champion_name.clone(),
+            kills: p.kills,
+            deaths: p.deaths,
+            assists: p.assists,
+            cs: p.total_minions_killed + p.neutral_minions_killed,
+            gold: p.gold_earned,
+            damage: p.total_damage_dealt_to_champions,
+            vision: p.vision_score,
+            win: p.win,
+            level: p.champ_level,
+            damage_taken: p.total_damage_taken,
+            wards_placed: p.wards_placed,
+            wards_killed: p.wards_killed,
+            double_kills: p.double_kills,
+            triple_kills: p.triple_kills,
+            quadra_kills: p.quadra_kills,
+            penta_kills: p.penta_kills,
+            spells: [spell_name(p.summoner1_id as i64), spell_name(p.summoner2_id as i64)],
+            keystone,
+            secondary,
+            items: participant_items(p),
+            metrics: Metrics::new(m, p),
+        }
+    }
+}
+
+/// `win` is from the point of view of the player that was searched
+#[derive(Serialize)]
+pub struct GameJson {
+    pub match_id: String,
+    pub start_time: i64,
+    pub duration: i64,
+    pub queue_id: u16,
+    pub queue: String,
+    pub game_mode: String,
+    pub map: String,
+    pub win: Option<bool>,
+    pub participants: Vec<ParticipantJson>,
+}
+
+impl GameJson {
+    pub fn new(m: &Match, puuid: &str) -> GameJson {
+        let info = &m.info;
+        GameJson {
+            match_id: m.metadata.match_id.clone(),
+            start_time: info.game_start_timestamp,
+            duration: duration_secs(info),
+            queue_id: info.queue_id.0,
+            queue: queue_name(info.queue_id.0),
+            game_mode: info.game_mode.to_string(),
+            map: map_name(info.map_id.0 as i64),
+            win: info
+                .participants
+                .iter()
+                .find(|p| p.puuid == puuid)
+                .map(|p| p.win),
+            participants: info.participants.iter().map(|p| ParticipantJson::new(m, p)).collect(),
+        }
+    }
+}
+
+/// one row per participant, shared by `games` and `history` in csv/tsv
+impl Table for GameJson {
+    const HEADER: &'static [&'static str] = &[
+        "match_id", "date", "queue", "duration", "participant", "team", "champion", "role",
+        "kills", "deaths", "assists", "kda", "cs", "gold", "damage", "vision", "win",
+        "cs_per_min", "kill_participation", "damage_share", "gold_per_min", "vision_per_min",
+        "damage_per_gold", "death_share",
+    ];
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.participants
+            .iter()
+            .map(|p| {
+                vec![
+                    self.match_id.clone(),
+                    format_date(self.start_time),
+                    queue_name(self.queue_id),
+                    self.duration.to_string(),
+                    p.riot_id.clone(),
+                    p.team.to_string(),
+                    p.champion.clone(),
+                    p.position.clone(),
+                    p.kills.to_string(),
+                    p.deaths.to_string(),
+                    p.assists.to_string(),
+                    format!("{:.2}", (p.kills + p.assists) as f64 / p.deaths.max(1) as f64),
+                    p.cs.to_string(),
+                    p.gold.to_string(),
+                    p.damage.to_string(),
+                    p.vision.to_string(),
+                    p.win.to_string(),
+                    format!("{:.2}", p.metrics.cs_per_min),
+                    format!("{:.3}", p.metrics.kill_participation),
+                    format!("{:.3}", p.metrics.damage_share),
+                    format!("{:.1}", p.metrics.gold_per_min),
+                    format!("{:.2}", p.metrics.vision_per_min),
+                    format!("{:.2}", p.metrics.damage_per_gold),
+                    format!("{:.3}", p.metrics.death_share),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl Table for Vec<GameJson> {
+    const HEADER: &'static [&'static str] = GameJson::HEADER;
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.iter().flat_map(|g| g.rows()).collect()
+    }
+}
+
+#[derive(Serialize)]
+pub struct RecordJson {
+    pub name: String,
+    pub games: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub win_rate: f64,
+    pub kda: f64,
+}
+
+impl RecordJson {
+    fn new(name: &str, r: &summary::Record) -> RecordJson {
+        RecordJson {
+            name: name.to_string(),
+            games: r.games,
+            wins: r.wins,
+            losses: r.losses(),
+            win_rate: r.win_rate(),
+            kda: r.kda(),
+        }
+    }
+
+    fn row(&self, group: &str) -> Vec<String> {
+        vec![
+            group.to_string(),
+            self.name.clone(),
+            self.games.to_string(),
+            self.wins.to_string(),
+            self.losses.to_string(),
+            format!("{:.3}", self.win_rate),
+            format!("{:.2}", self.kda),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+pub struct SummaryJson {
+    pub overall: RecordJson,
+    pub champions: Vec<RecordJson>,
+    pub roles: Vec<RecordJson>,
+    pub best: Option<GameStat>,
+    pub worst: Option<GameStat>,
+    pub streak: i32,
+}
+
+impl From<&Summary> for SummaryJson {
+    fn from(s: &Summary) -> Self {
+        SummaryJson {
+            overall: RecordJson::new("", &s.overall),
+            champions: s.champions.iter().map(|(n, r)| RecordJson::new(n, r)).collect(),
+            roles: s.roles.iter().map(|(n, r)| RecordJson::new(n, r)).collect(),
+            best: s.best.clone(),
+            worst: s.worst.clone(),
+            streak: s.streak,
+        }
+    }
+}
+
+/// one row for the overall record and one per champion and role, best/worst and streak are json only
+impl Table for SummaryJson {
+    const HEADER: &'static [&'static str] = &["group", "name", "games", "wins", "losses", "win_rate", "kda"];
+    fn rows(&self) -> Vec<Vec<String>> {
+        let mut rows = vec![self.overall.row("overall")];
+        rows.extend(self.champions.iter().map(|r| r.row("champion")));
+        rows.extend(self.roles.iter().map(|r| r.row("role")));
+        rows
+    }
+}
+
+#[derive(Serialize)]
+pub struct CacheJson {
+    pub dir: String,
+    pub total_bytes: u64,
+    pub kinds: Vec<CacheKindJson>,
+}
+
+#[derive(Serialize)]
+pub struct CacheKindJson {
+    pub kind: String,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+impl Table for CacheJson {
+    const HEADER: &'static [&'static str] = &["dir", "kind", "files", "bytes"];
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.kinds
+            .iter()
+            .map(|k| {
+                vec![
+                    self.dir.clone(),
+                    k.kind.clone(),
+                    k.files.to_string(),
+                    k.bytes.to_string(),
+                ]
+            })
+            .collect()
+    }
+}
+
+#[derive(Serialize)]
+pub struct ErrorJson {
+    pub error: String,
+    pub exit_code: i32,
+}
+
+impl From<&WatcherError> for ErrorJson {
+    fn from(e: &WatcherError) -> Self {
+        ErrorJson {
+            error: e.to_string(),
+            exit_code: e.exit_code(),
+        }
+    }
+}
+
+/// errors already went to stderr, they don't get rows
+impl Table for ErrorJson {
+    const HEADER: &'static [&'static str] = &[];
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![]
+    }
+}
+
+#[derive(Serialize)]
+pub struct LiveJson {
+    pub game_id: i64,
+    pub game_mode: String,
+    pub map: String,
+    pub queue: String,
+    pub elapsed: i64,
+    pub bans: Vec<LiveBanJson>,
+    pub participants: Vec<LiveParticipantJson>,
+}
+
+#[derive(Serialize)]
+pub struct LiveBanJson {
+    pub team: &'static str,
+    pub champion: String,
+}
+
+#[derive(Serialize)]
+pub struct LiveParticipantJson {
+    pub riot_id: String,
+    pub puuid: String,
+    pub team: &'static str,
+    pub champion: String,
+    pub spells: [String; 2],
+    pub keystone: String,
+    pub secondary: String,
+    pub rank: Option<RankJson>,
+}
+
+impl From<&LiveGame> for LiveJson {
+    fn from(live: &LiveGame) -> Self {
+        let game = &live.game;
+        LiveJson {
+            game_id: game.game_id,
+            game_mode: game.game_mode.clone(),
+            map: map_name(game.map_id),
+            queue: game
+                .game_queue_config_id
+                .map(|q| queue_name(q.0))
+                .unwrap_or_default(),
+            elapsed: game.elapsed(),
+            bans: game
+                .banned_champions
+                .iter()
+                .filter(|b| b.champion_id.0 >= 0)
+                .map(|b| LiveBanJson {
+                    team: team_name(b.team_id),
+                    champion: champion_name(b.champion_id),
+                })
+                .collect(),
+            participants: game
+                .participants
+                .iter()
+                .zip(&live.ranks)
+                .map(|(p, rank)| LiveParticipantJson {
+                    riot_id: p.riot_id.clone().unwrap_or_default(),
+                    puuid: p.puuid.clone().unwrap_or_default(),
+                    team: team_name(p.team_id),
+                    champion: champion_name(p.champion_id),
+                    spells: [spell_name(p.spell1_id), spell_name(p.spell2_id)],
+                    keystone: p
+                        .perks
+                        .as_ref()
+                        .and_then(|perks| perks.perk_ids.first())
+                        .map(|id| rune_name(*id))
+                        .unwrap_or_default(),
+                    secondary: p
+                        .perks
+                        .as_ref()
+                        .map(|perks| rune_name(perks.perk_sub_style))
+                        .unwrap_or_default(),
+                    rank: rank.as_ref().map(RankJson::from),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// one row per participant
+impl Table for LiveJson {
+    const HEADER: &'static [&'static str] = &[
+        "game_id", "queue", "elapsed", "participant", "team", "champion", "spell1", "spell2", "keystone",
+        "secondary", "tier", "division", "league_points",
+    ];
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.participants
+            .iter()
+            .map(|p| {
+                vec![
+                    self.game_id.to_string(),
+                    self.queue.clone(),
+                    self.elapsed.to_string(),
+                    p.riot_id.clone(),
+                    p.team.to_string(),
+                    p.champion.clone(),
+                    p.spells[0].clone(),
+                    p.spells[1].clone(),
+                    p.keystone.clone(),
+                    p.secondary.clone(),
+                    p.rank.as_ref().and_then(|r| r.tier.clone()).unwrap_or_default(),
+                    p.rank.as_ref().and_then(|r| r.division.clone()).unwrap_or_default(),
+                    p.rank.as_ref().map(|r| r.league_points.to_string()).unwrap_or_default(),
+                ]
+            })
+            .collect()
+    }
+}
+
+pub fn team_name(team: Team) -> &'static str {
+    match team {
+        Team::BLUE => "blue",
+        Team::RED => "red",
+        _ => "other",
+    }
+}
+
+/// where the CLI printers write to, text goes straight to stdout,
+/// json is collected until `finish`
+pub struct Output {
+    pub format: Format,
+    records: Vec<Value>,
+    /// kind of the last csv/tsv header printed
+    header: Option<&'static str>,
+    sink: Box<dyn Write>,
+}
+
+impl Output {
+    pub fn new(format: Format) -> Output {
+        Output::with_sink(format, Box::new(io::stdout()))
+    }
+
+    /// same records written to `sink` instead of stdout, the TUI export
+    pub fn with_sink(format: Format, sink: Box<dyn Write>) -> Output {
+        Output {
+            format,
+            records: vec![],
+            header: None,
+            sink,
+        }
+    }
+
+    pub fn emit<T: Serialize + Table>(
+        &mut self,
+        kind: &'static str,
+        player: &str,
+        region: &str,
+        data: T,
+        text: impl Display,
+    ) {
+        let record = Record {
+            schema_version: SCHEMA_VERSION,
+            kind,
+            player: player.to_string(),
+            region: region.to_string(),
+            data,
+        };
+        match self.format {
+            Format::Text => {
+                let _ = write!(self.sink, "{}", text);
+            }
+            Format::Ndjson => {
+                if let Ok(line) = serde_json::to_string(&record) {
+                    let _ = writeln!(self.sink, "{}", line);
+                }
+            }
+            Format::Json => {
+                if let Ok(value) = serde_json::to_value(&record) {
+                    self.records.push(value);
+                }
+            }
+            Format::Csv => self.table(kind, &record, ','),
+            Format::Tsv => self.table(kind, &record, '\t'),
+        }
+    }
+
+    fn table<T: Serialize + Table>(&mut self, kind: &'static str, record: &Record<T>, sep: char) {
+        let rows = record.data.rows();
+        if rows.is_empty() {
+            return;
+        }
+        if self.header != Some(kind) {
+            let header = ["player", "region"]
+                .iter()
+                .chain(T::HEADER)
+                .map(|f| f.to_string())
+                .collect::<Vec<_>>();
+            let _ = writeln!(self.sink, "{}", join_row(&header, sep));
+            self.header = Some(kind);
+        }
+        for row in rows {
+            let row = [record.player.clone(), record.region.clone()]
+                .into_iter()
+                .chain(row)
+                .collect::<Vec<_>>();
+            let _ = writeln!(self.sink, "{}", join_row(&row, sep));
+        }
+    }
+
+    pub fn finish(&mut self) {
+        if self.format == Format::Json {
+            let doc = serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "results": self.records,
+            });
+            if let Ok(text) = serde_json::to_string_pretty(&doc) {
+                let _ = writeln!(self.sink, "{}", text);
+            }
+        }
+        let _ = self.sink.flush();
+    }
+}
+
+/// csv quotes fields with separators, quotes or newlines, tsv can't quote so they become spaces
+fn join_row(row: &[String], sep: char) -> String {
+    row.iter()
+        .map(|field| {
+            if sep == '\t' {
+                field.replace(['\t', '\n', '\r'], " ")
+            } else if field.contains([sep, '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(&sep.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::join_row;
+
+    #[test]
+    fn quoting() {
+        let row = ["plain", "a,b", "say \"gg\"", "two\nlines", "tab\there"].map(String::from);
+        assert_eq!(
+            join_row(&row, ','),
+            "plain,\"a,b\",\"say \"\"gg\"\"\",\"two\nlines\",tab\there"
+        );
+        assert_eq!(
+            join_row(&row, '\t'),
+            "plain\ta,b\tsay \"gg\"\ttwo lines\ttab here"
+        );
+    }
+}
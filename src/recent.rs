@@ -0,0 +1,52 @@
+use std::{fs, path::PathBuf};
+
+use riven::consts::PlatformRoute;
+
+use crate::utils::route_name;
+
+pub const RECENT_FILE: &str = "recent";
+/// oldest searches are dropped past this
+pub const MAX_RECENT: usize = 100;
+
+/// ~/.local/share/watcher/recent, one `name<TAB>region` per line, newest first
+pub fn recent_path() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|d| d.join("watcher").join(RECENT_FILE))
+}
+
+pub fn load() -> Vec<(String, String)> {
+    let text = match recent_path().and_then(|p| fs::read_to_string(p).ok()) {
+        Some(text) => text,
+        None => return vec![],
+    };
+    text.lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(name, region)| (name.to_string(), region.to_string()))
+        .collect()
+}
+
+/// moves `name` to the top, failing to write is not worth an error.
+/// Only for searches the user typed, batch lookups would push them out
+pub fn push(name: &str, route: PlatformRoute) {
+    let path = match recent_path() {
+        Some(path) => path,
+        None => return,
+    };
+    let region = route_name(route).unwrap_or_default();
+    let mut recent = load();
+    recent.retain(|(n, _)| !n.eq_ignore_ascii_case(name));
+    recent.insert(0, (name.to_string(), region));
+    recent.truncate(MAX_RECENT);
+
+    let text = recent
+        .iter()
+        .map(|(name, region)| format!("{}\t{}\n", name, region))
+        .collect::<String>();
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    // written next to it then renamed, a reader never sees half a file
+    let tmp = path.with_extension(format!("tmp{}", std::process::id()));
+    if fs::write(&tmp, text).is_ok() && fs::rename(&tmp, &path).is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+}
@@ -0,0 +1,59 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::Path,
+};
+
+use riven::consts::PlatformRoute;
+
+use crate::utils::route_by_name;
+
+/// `-` reads stdin
+pub fn read(path: &Path) -> Result<String, String> {
+    let mut text = String::new();
+    if path.as_os_str() == "-" {
+        io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| format!("couldn't read stdin: {}", e))?;
+        return Ok(text);
+    }
+    fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {}", path.display(), e))
+}
+
+/// one `name[#tag] [region]` per line, blank lines and lines starting with `#` are skipped,
+/// the last word is only taken as the region when it is one of ROUTE_NAMES
+pub fn parse(text: &str) -> Vec<(String, Option<PlatformRoute>)> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| match line.rsplit_once(char::is_whitespace) {
+            Some((name, region)) => match route_by_name(region) {
+                Some(route) => (name.trim().to_string(), Some(route)),
+                None => (line.to_string(), None),
+            },
+            None => (line.to_string(), None),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use riven::consts::PlatformRoute;
+
+    use super::parse;
+
+    #[test]
+    fn names_and_regions() {
+        let roster = "Faker#KR1 kr\n\n# tryouts\nCaps#EUW euw\nHide on bush\n@main\n  Keria#KR1   KR  \n";
+        assert_eq!(
+            parse(roster),
+            vec![
+                ("Faker#KR1".to_string(), Some(PlatformRoute::KR)),
+                ("Caps#EUW".to_string(), Some(PlatformRoute::EUW1)),
+                ("Hide on bush".to_string(), None),
+                ("@main".to_string(), None),
+                ("Keria#KR1".to_string(), Some(PlatformRoute::KR)),
+            ]
+        );
+    }
+}
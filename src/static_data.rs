@@ -0,0 +1,394 @@
+//! names for the ids riven doesn't resolve itself, an imported Data Dragon
+//! snapshot wins over the built in tables
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use riven::consts::Champion;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub const DDRAGON_FILE: &str = "ddragon.json";
+
+/// files looked for by `watcher ddragon import`, queues and maps come from
+/// static.developer.riotgames.com, the rest from the ddragon tarball
+pub const DDRAGON_FILES: [&str; 6] = [
+    "champion.json",
+    "item.json",
+    "runesReforged.json",
+    "summoner.json",
+    "queues.json",
+    "maps.json",
+];
+
+/// what an import keeps, names by id
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StaticData {
+    pub version: Option<String>,
+    pub champions: BTreeMap<i64, String>,
+    pub items: BTreeMap<i64, String>,
+    pub runes: BTreeMap<i64, String>,
+    pub spells: BTreeMap<i64, String>,
+    pub queues: BTreeMap<i64, String>,
+    pub maps: BTreeMap<i64, String>,
+}
+
+static DATA: OnceLock<StaticData> = OnceLock::new();
+
+/// ~/.local/share/watcher/ddragon.json
+pub fn ddragon_path() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|d| d.join("watcher").join(DDRAGON_FILE))
+}
+
+/// read once, an empty set when nothing was imported
+fn data() -> &'static StaticData {
+    DATA.get_or_init(|| {
+        ddragon_path()
+            .and_then(|p| fs::read_to_string(p).ok())
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    })
+}
+
+impl StaticData {
+    /// walks `dir` for DDRAGON_FILES, en_US and the newest version win when there are several
+    pub fn import(dir: &Path) -> Result<StaticData, String> {
+        let mut files = vec![];
+        walk(dir, &mut files).map_err(|e| format!("couldn't read {}: {}", dir.display(), e))?;
+
+        let mut data = StaticData::default();
+        let mut found = 0;
+        for name in DDRAGON_FILES {
+            let path = match best(&files, name) {
+                Some(path) => path,
+                None => continue,
+            };
+            let text = fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+            let json: Value =
+                serde_json::from_str(&text).map_err(|e| format!("couldn't parse {}: {}", path.display(), e))?;
+            match name {
+                "champion.json" => data.champions = keyed(&json),
+                "item.json" => {
+                    data.version = json["version"].as_str().map(|v| v.to_string());
+                    data.items = by_id(&json)
+                }
+                "runesReforged.json" => data.runes = runes(&json),
+                "summoner.json" => data.spells = keyed(&json),
+                "queues.json" => data.queues = list(&json, "queueId", "description"),
+                "maps.json" => data.maps = list(&json, "mapId", "mapName"),
+                _ => {}
+            }
+            found += 1;
+        }
+        if found == 0 {
+            return Err(format!("no {} under {}", DDRAGON_FILES.join(", "), dir.display()));
+        }
+        Ok(data)
+    }
+
+    pub fn save(&self) -> Result<PathBuf, String> {
+        let path = ddragon_path().ok_or("no data dir")?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("couldn't create {}: {}", parent.display(), e))?;
+        }
+        let text = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        fs::write(&path, text).map_err(|e| format!("couldn't write {}: {}", path.display(), e))?;
+        Ok(path)
+    }
+}
+
+fn walk(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            walk(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// en_US first, then the highest `14.10.1/` directory, then the path so the pick is stable
+fn best<'a>(files: &'a [PathBuf], name: &str) -> Option<&'a PathBuf> {
+    files
+        .iter()
+        .filter(|p| p.file_name().map(|f| f == name).unwrap_or(false))
+        .max_by_key(|p| {
+            let path = p.to_string_lossy();
+            (path.contains("en_US"), version_of(p), path.to_string())
+        })
+}
+
+/// the last `N.N.N` directory in `path` as numbers, so 14.10 is newer than 14.9
+fn version_of(path: &Path) -> Option<Vec<u32>> {
+    path.components().rev().find_map(|c| {
+        let parts = c.as_os_str().to_str()?.split('.').map(|n| n.parse::<u32>().ok()).collect::<Option<Vec<_>>>()?;
+        (parts.len() > 1).then_some(parts)
+    })
+}
+
+/// `{"data": {"1001": {"name": ..}}}`, items
+fn by_id(json: &Value) -> BTreeMap<i64, String> {
+    json["data"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(id, v)| Some((id.parse().ok()?, v["name"].as_str()?.to_string())))
+        .collect()
+}
+
+/// `{"data": {"SummonerFlash": {"key": "4", "name": ..}}}`, champions and spells
+fn keyed(json: &Value) -> BTreeMap<i64, String> {
+    json["data"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(_, v)| Some((v["key"].as_str()?.parse().ok()?, v["name"].as_str()?.to_string())))
+        .collect()
+}
+
+/// trees and every rune in their slots
+fn runes(json: &Value) -> BTreeMap<i64, String> {
+    let mut runes = BTreeMap::new();
+    for tree in json.as_array().into_iter().flatten() {
+        if let (Some(id), Some(name)) = (tree["id"].as_i64(), tree["name"].as_str()) {
+            runes.insert(id, name.to_string());
+        }
+        for slot in tree["slots"].as_array().into_iter().flatten() {
+            for rune in slot["runes"].as_array().into_iter().flatten() {
+                if let (Some(id), Some(name)) = (rune["id"].as_i64(), rune["name"].as_str()) {
+                    runes.insert(id, name.to_string());
+                }
+            }
+        }
+    }
+    runes
+}
+
+/// `[{"queueId": 420, "description": ..}]`, queues and maps
+fn list(json: &Value, id: &str, name: &str) -> BTreeMap<i64, String> {
+    json.as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|v| Some((v[id].as_i64()?, v[name].as_str()?.to_string())))
+        .collect()
+}
+
+pub const SPELLS: [(i64, &str); 14] = [
+    (1, "Cleanse"),
+    (3, "Exhaust"),
+    (4, "Flash"),
+    (6, "Ghost"),
+    (7, "Heal"),
+    (11, "Smite"),
+    (12, "Teleport"),
+    (13, "Clarity"),
+    (14, "Ignite"),
+    (21, "Barrier"),
+    (30, "To the King!"),
+    (31, "Poro Toss"),
+    (32, "Mark"),
+    (39, "Mark"),
+];
+
+/// rune trees and keystones, the minor runes are left as ids
+pub const RUNES: [(i64, &str); 22] = [
+    (8000, "Precision"),
+    (8100, "Domination"),
+    (8200, "Sorcery"),
+    (8300, "Inspiration"),
+    (8400, "Resolve"),
+    (8005, "Press the Attack"),
+    (8008, "Lethal Tempo"),
+    (8010, "Conqueror"),
+    (8021, "Fleet Footwork"),
+    (8112, "Electrocute"),
+    (8124, "Predator"),
+    (8128, "Dark Harvest"),
+    (9923, "Hail of Blades"),
+    (8214, "Summon Aery"),
+    (8229, "Arcane Comet"),
+    (8230, "Phase Rush"),
+    (8351, "Glacial Augment"),
+    (8360, "Unsealed Spellbook"),
+    (8369, "First Strike"),
+    (8437, "Grasp of the Undying"),
+    (8439, "Aftershock"),
+    (8465, "Guardian"),
+];
+
+/// boots, starters, wards and the usual finished items, anything else shows its id
+pub const ITEMS: [(i64, &str); 71] = [
+    (1001, "Boots"),
+    (1054, "Doran's Shield"),
+    (1055, "Doran's Blade"),
+    (1056, "Doran's Ring"),
+    (1083, "Cull"),
+    (2003, "Health Potion"),
+    (2031, "Refillable Potion"),
+    (2055, "Control Ward"),
+    (2065, "Shurelya's Battlesong"),
+    (3003, "Archangel's Staff"),
+    (3004, "Manamune"),
+    (3006, "Berserker's Greaves"),
+    (3009, "Boots of Swiftness"),
+    (3020, "Sorcerer's Shoes"),
+    (3026, "Guardian Angel"),
+    (3031, "Infinity Edge"),
+    (3033, "Mortal Reminder"),
+    (3036, "Lord Dominik's Regards"),
+    (3040, "Seraph's Embrace"),
+    (3042, "Muramana"),
+    (3046, "Phantom Dancer"),
+    (3047, "Plated Steelcaps"),
+    (3050, "Zeke's Convergence"),
+    (3053, "Sterak's Gage"),
+    (3065, "Spirit Visage"),
+    (3068, "Sunfire Aegis"),
+    (3071, "Black Cleaver"),
+    (3072, "Bloodthirster"),
+    (3074, "Ravenous Hydra"),
+    (3075, "Thornmail"),
+    (3078, "Trinity Force"),
+    (3083, "Warmog's Armor"),
+    (3085, "Runaan's Hurricane"),
+    (3087, "Statikk Shiv"),
+    (3089, "Rabadon's Deathcap"),
+    (3091, "Wit's End"),
+    (3094, "Rapid Firecannon"),
+    (3100, "Lich Bane"),
+    (3102, "Banshee's Veil"),
+    (3107, "Redemption"),
+    (3109, "Knight's Vow"),
+    (3110, "Frozen Heart"),
+    (3111, "Mercury's Treads"),
+    (3115, "Nashor's Tooth"),
+    (3116, "Rylai's Crystal Scepter"),
+    (3124, "Guinsoo's Rageblade"),
+    (3135, "Void Staff"),
+    (3139, "Mercurial Scimitar"),
+    (3142, "Youmuu's Ghostblade"),
+    (3143, "Randuin's Omen"),
+    (3153, "Blade of The Ruined King"),
+    (3156, "Maw of Malmortius"),
+    (3157, "Zhonya's Hourglass"),
+    (3158, "Ionian Boots of Lucidity"),
+    (3165, "Morellonomicon"),
+    (3190, "Locket of the Iron Solari"),
+    (3222, "Mikael's Blessing"),
+    (3340, "Stealth Ward"),
+    (3363, "Farsight Alteration"),
+    (3364, "Oracle Lens"),
+    (3504, "Ardent Censer"),
+    (3508, "Essence Reaver"),
+    (3742, "Dead Man's Plate"),
+    (3748, "Titanic Hydra"),
+    (3814, "Edge of Night"),
+    (6333, "Death's Dance"),
+    (6672, "Kraken Slayer"),
+    (6673, "Immortal Shieldbow"),
+    (6676, "The Collector"),
+    (6692, "Eclipse"),
+    (6694, "Serylda's Grudge"),
+];
+
+pub const MAPS: [(i64, &str); 4] = [
+    (11, "Summoner's Rift"),
+    (12, "Howling Abyss"),
+    (21, "Nexus Blitz"),
+    (30, "Rings of Wrath"),
+];
+
+fn lookup(imported: &BTreeMap<i64, String>, table: &[(i64, &str)], id: i64) -> String {
+    if let Some(name) = imported.get(&id) {
+        return name.clone();
+    }
+    table
+        .iter()
+        .find(|(key, _)| *key == id)
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// the id itself when it isn't known
+pub fn spell_name(id: i64) -> String {
+    lookup(&data().spells, &SPELLS, id)
+}
+
+pub fn rune_name(id: i64) -> String {
+    lookup(&data().runes, &RUNES, id)
+}
+
+/// empty for an empty slot
+pub fn item_name(id: i64) -> String {
+    if id == 0 {
+        return String::new();
+    }
+    lookup(&data().items, &ITEMS, id)
+}
+
+pub fn map_name(id: i64) -> String {
+    lookup(&data().maps, &MAPS, id)
+}
+
+/// the queue description, e.g. `5v5 Ranked Solo games`, only known after an import
+pub fn queue_description(id: i64) -> Option<String> {
+    data().queues.get(&id).cloned()
+}
+
+/// riven only knows the champions of its release
+pub fn champion_name(champion: Champion) -> String {
+    if let Some(name) = data().champions.get(&(champion.0 as i64)) {
+        return name.clone();
+    }
+    champion
+        .name()
+        .map(|n| n.to_string())
+        .unwrap_or_else(|| champion.0.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use serde_json::json;
+
+    use super::{best, by_id, keyed, list, runes};
+
+    #[test]
+    fn parses_ddragon_files() {
+        let items = json!({"version": "14.1.1", "data": {"1001": {"name": "Boots"}}});
+        assert_eq!(by_id(&items)[&1001], "Boots");
+
+        let spells = json!({"data": {"SummonerFlash": {"key": "4", "name": "Flash"}}});
+        assert_eq!(keyed(&spells)[&4], "Flash");
+
+        let trees = json!([{"id": 8100, "name": "Domination", "slots": [{"runes": [{"id": 8112, "name": "Electrocute"}]}]}]);
+        let runes = runes(&trees);
+        assert_eq!(runes[&8100], "Domination");
+        assert_eq!(runes[&8112], "Electrocute");
+
+        let queues = json!([{"queueId": 420, "map": "Summoner's Rift", "description": "5v5 Ranked Solo games"}]);
+        assert_eq!(list(&queues, "queueId", "description")[&420], "5v5 Ranked Solo games");
+    }
+
+    #[test]
+    fn newest_version_wins() {
+        let files = [
+            "dd/14.9.1/data/en_US/item.json",
+            "dd/14.10.1/data/en_US/item.json",
+            "dd/14.10.1/data/de_DE/item.json",
+            "dd/14.2.1/data/en_US/item.json",
+        ]
+        .map(PathBuf::from);
+        assert_eq!(best(&files, "item.json"), Some(&files[1]));
+        assert_eq!(best(&files[2..], "item.json"), Some(&files[3]));
+        assert_eq!(best(&files, "champion.json"), None);
+    }
+}
@@ -0,0 +1,192 @@
+//! one player's fetched games added up
+
+use std::cmp::Reverse;
+
+use riven::models::match_v5::{Match, Participant};
+use serde::Serialize;
+
+/// wins and kda over some games
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Record {
+    pub games: u32,
+    pub wins: u32,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+}
+
+impl Record {
+    fn add(&mut self, game: &GameStat) {
+        self.games += 1;
+        self.wins += game.win as u32;
+        self.kills += game.kills;
+        self.deaths += game.deaths;
+        self.assists += game.assists;
+    }
+
+    pub fn losses(&self) -> u32 {
+        self.games - self.wins
+    }
+
+    /// 0..1
+    pub fn win_rate(&self) -> f64 {
+        match self.games {
+            0 => 0.0,
+            n => self.wins as f64 / n as f64,
+        }
+    }
+
+    /// (kills + assists) / deaths, deaths count as 1 when there are none
+    pub fn kda(&self) -> f64 {
+        (self.kills + self.assists) as f64 / self.deaths.max(1) as f64
+    }
+}
+
+/// the player's line of one game
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GameStat {
+    pub match_id: String,
+    pub champion: String,
+    pub role: String,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    pub win: bool,
+}
+
+impl GameStat {
+    pub fn of(game: &Match, p: &Participant) -> GameStat {
+        GameStat {
+            match_id: game.metadata.match_id.clone(),
+            champion: p.champion_name.clone(),
+            role: match p.team_position.as_str() {
+                "" => "NONE".to_string(),
+                role => role.to_string(),
+            },
+            kills: p.kills,
+            deaths: p.deaths,
+            assists: p.assists,
+            win: p.win,
+        }
+    }
+
+    pub fn kda(&self) -> f64 {
+        (self.kills + self.assists) as f64 / self.deaths.max(1) as f64
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Summary {
+    pub overall: Record,
+    /// most played first
+    pub champions: Vec<(String, Record)>,
+    pub roles: Vec<(String, Record)>,
+    /// highest and lowest kda
+    pub best: Option<GameStat>,
+    pub worst: Option<GameStat>,
+    /// positive for wins in a row, negative for losses, counted from the newest game
+    pub streak: i32,
+}
+
+impl Summary {
+    /// `games` newest first like the match history, games without `puuid` are skipped
+    pub fn new(games: &[Match], puuid: &str) -> Summary {
+        let stats = games
+            .iter()
+            .filter_map(|g| {
+                g.info
+                    .participants
+                    .iter()
+                    .find(|p| p.puuid == puuid)
+                    .map(|p| GameStat::of(g, p))
+            })
+            .collect::<Vec<_>>();
+        Summary::from_stats(&stats)
+    }
+
+    pub fn from_stats(stats: &[GameStat]) -> Summary {
+        let mut summary = Summary::default();
+        for game in stats {
+            summary.overall.add(game);
+            record_of(&mut summary.champions, &game.champion).add(game);
+            record_of(&mut summary.roles, &game.role).add(game);
+        }
+        // stable, ties keep the order they were first played in
+        summary.champions.sort_by_key(|(_, r)| Reverse(r.games));
+        summary.roles.sort_by_key(|(_, r)| Reverse(r.games));
+
+        summary.best = stats.iter().max_by(|a, b| a.kda().total_cmp(&b.kda())).cloned();
+        summary.worst = stats.iter().min_by(|a, b| a.kda().total_cmp(&b.kda())).cloned();
+
+        if let Some(first) = stats.first() {
+            let len = stats.iter().take_while(|g| g.win == first.win).count() as i32;
+            summary.streak = if first.win { len } else { -len };
+        }
+        summary
+    }
+}
+
+fn record_of<'a>(records: &'a mut Vec<(String, Record)>, name: &str) -> &'a mut Record {
+    let i = match records.iter().position(|(n, _)| n == name) {
+        Some(i) => i,
+        None => {
+            records.push((name.to_string(), Record::default()));
+            records.len() - 1
+        }
+    };
+    &mut records[i].1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GameStat, Summary};
+
+    fn game(id: &str, champion: &str, role: &str, kda: (i32, i32, i32), win: bool) -> GameStat {
+        GameStat {
+            match_id: id.to_string(),
+            champion: champion.to_string(),
+            role: role.to_string(),
+            kills: kda.0,
+            deaths: kda.1,
+            assists: kda.2,
+            win,
+        }
+    }
+
+    #[test]
+    fn summary_of_games() {
+        let stats = vec![
+            game("4", "Ahri", "MIDDLE", (10, 1, 5), true),
+            game("3", "Ahri", "MIDDLE", (2, 6, 3), true),
+            game("2", "Zed", "MIDDLE", (5, 5, 0), false),
+            game("1", "Leona", "UTILITY", (0, 3, 20), true),
+        ];
+        let s = Summary::from_stats(&stats);
+        assert_eq!(s.overall.games, 4);
+        assert_eq!(s.overall.wins, 3);
+        assert_eq!(s.overall.win_rate(), 0.75);
+        assert_eq!(s.overall.kda(), 45.0 / 15.0);
+        assert_eq!(s.champions[0].0, "Ahri");
+        assert_eq!(s.champions[0].1.games, 2);
+        assert_eq!(s.roles[0].0, "MIDDLE");
+        assert_eq!(s.roles[0].1.losses(), 1);
+        assert_eq!(s.best.unwrap().match_id, "4");
+        assert_eq!(s.worst.unwrap().match_id, "3");
+        assert_eq!(s.streak, 2);
+    }
+
+    #[test]
+    fn losing_streak_and_no_games() {
+        let stats = vec![
+            game("2", "Zed", "MIDDLE", (0, 1, 0), false),
+            game("1", "Zed", "MIDDLE", (0, 1, 0), true),
+        ];
+        assert_eq!(Summary::from_stats(&stats).streak, -1);
+
+        let s = Summary::from_stats(&[]);
+        assert_eq!(s.overall.games, 0);
+        assert_eq!(s.overall.win_rate(), 0.0);
+        assert!(s.best.is_none());
+        assert_eq!(s.streak, 0);
+    }
+}
@@ -1,29 +1,73 @@
-use std::fmt::Error;
+use std::{fmt::Display, fs::File, future::Future, io::BufWriter, sync::Arc};
 
 use ratatui::{style::Style, text::Span, widgets::ListState};
-use riven::consts::PlatformRoute;
+use riven::{consts::PlatformRoute, models::league_v4::LeagueEntry};
+use tokio::task::JoinHandle;
 
 use crate::{
-    api::api::{get_games, get_masteries, get_rank, get_summoner},
-    display::{ChampionMasteryDisplay, LeagueEntryDisplay, MatchDisplay, SummonerDisplay, With}, utils::{Log, routes, parse_route},
+    api::{cache::Kind, api::{get_games, get_live_game, get_masteries, get_rank, get_summoner, get_timeline, MatchQuery}, client::WatcherClient, player::{Player, RiotId}, timeline::GameTimeline},
+    error::WatcherError,
+    logs,
+    recent,
+    output::{Format, GameJson, Output},
+    summary::Summary,
+    config::{Config, Profile},
+    display::{ChampionMasteryDisplay, LeagueEntryDisplay, LiveGameDisplay, MatchDisplay, SummaryDisplay, SummonerDisplay, With}, utils::{Log, LogKind, routes, parse_route},
 };
 
-use super::keys::Keys;
+use super::{
+    keys::Keys,
+    palette::{Command, Palette},
+    tasks::{Load, Loads, Sender, Tagged, Update},
+};
+
+/// top champions fetched for the masteries panel
+const MASTERIES: usize = 10;
 
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
 pub enum State {
     Searching(String, PlatformRoute), // name PlatformRoute
+    /// no summoner with that name
     Failed(String, PlatformRoute),
+    /// the summoner couldn't be fetched at all
     Error(WatcherErr),
     Idle,
 }
 
+/// shown in a popup until a key is pressed
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
 pub enum WatcherErr {
-    SearchFalied { name: String, puuid: String },
-    Riot(String),
+    SearchFalied { name: String, route: PlatformRoute },
+    Riot(WatcherError),
+}
+
+impl WatcherErr {
+    pub fn title(&self) -> &'static str {
+        match self {
+            WatcherErr::SearchFalied { .. } => "not found",
+            WatcherErr::Riot(WatcherError::Forbidden) => "API key expired",
+            WatcherErr::Riot(WatcherError::RateLimited(_)) => "rate limited",
+            WatcherErr::Riot(WatcherError::Network(_)) => "network down",
+            WatcherErr::Riot(_) => "riot api error",
+        }
+    }
+
+    /// errors that break every request, not just the panel they happened in
+    fn global(err: &WatcherError) -> bool {
+        matches!(
+            err,
+            WatcherError::Forbidden | WatcherError::RateLimited(_) | WatcherError::Network(_)
+        )
+    }
+}
+
+impl Display for WatcherErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WatcherErr::SearchFalied { name, route } => write!(f, "no summoner {} on {}", name, route),
+            WatcherErr::Riot(e) => write!(f, "{}", e),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -31,6 +75,11 @@ pub enum Msg {
     Quit,
     Focus(Window),
     Search(PlatformRoute, String),
+    LoadMore,
+    /// show or hide the timeline of the selected game
+    Timeline,
+    /// stop the search in flight, Esc while something is loading
+    Cancel,
     None,
 }
 
@@ -43,7 +92,19 @@ pub enum Window {
     Masteries,
     List,
     Games,
+    /// game in progress, drawn in place of the games, not part of the tab cycle
+    Live,
+    /// the loaded games added up, drawn in place of the games
+    Summary,
     Footer,
+    /// popup, not part of the tab cycle
+    Profiles,
+    /// full screen log history, not part of the tab cycle
+    Logs,
+    /// `?` overlay, not part of the tab cycle
+    Help,
+    /// `:` commands, not part of the tab cycle
+    Palette,
 }
 
 impl Window {
@@ -56,6 +117,7 @@ impl Window {
             Window::Masteries,
             Window::List,
             Window::Games,
+            Window::Summary,
             Window::Footer,
         ];
         for (i, w) in windows.iter().enumerate() {
@@ -70,9 +132,8 @@ impl Window {
     }
 }
 
-#[derive(Clone)]
 pub struct App {
-    pub api_key: String, 
+    pub client: Arc<WatcherClient>,
     pub state: State,
     pub msg: Option<Msg>,
     pub focus: Option<Window>,
@@ -80,18 +141,76 @@ pub struct App {
     pub input: Input,
     pub route: PlatformRoute,
     pub routes: RouteList,
+    pub profiles: ProfileList,
     pub keys: Keys,
     pub log: Log,
+    pub log_view: LogView,
+    pub palette: Palette,
+    /// items, runes and damage instead of the short scoreboard
+    pub full_scoreboard: bool,
+    pub loads: Loads,
+    pub popup: Option<WatcherErr>,
+    /// bumped every frame, drives the spinners
+    pub tick: usize,
+    /// id of the current search, updates of older ones are dropped
+    search: u64,
+    /// `:refresh` of a player whose live game was open
+    refresh_live: bool,
+    tx: Sender,
+    tasks: Vec<JoinHandle<()>>,
 }
 
 #[derive(Clone)]
 pub struct Data {
     pub rank: Option<Vec<LeagueEntryDisplay>>,
-    pub current_search: Option<(String, String)>, // (id,name)
-    pub env_search: Option<(String, String)>,
+    pub current_search: Option<Player>,
+    pub search_route: Option<PlatformRoute>,
+    /// name as it was searched, the summoner is cached under it and not under the Riot ID it resolved to
+    pub search_name: Option<String>,
     pub masteries: Option<Vec<ChampionMasteryDisplay>>,
     pub summoner: Option<SummonerDisplay>,
     pub games: Games,
+    pub live: Option<LiveGameDisplay>,
+    /// set once a live fetch returned, None afterwards means not in a game
+    pub live_checked: bool,
+    /// drawn instead of the scoreboard while its game is selected
+    pub timeline: Option<GameTimeline>,
+    /// of every game in the list, redone when more are loaded
+    pub summary: Option<SummaryDisplay>,
+}
+
+/// state of the full screen log view
+#[derive(Debug, Clone)]
+pub struct LogView {
+    /// entries hidden below, 0 follows the newest
+    pub scroll: usize,
+    /// lowest level shown
+    pub level: LogKind,
+}
+
+impl Default for LogView {
+    fn default() -> Self {
+        LogView {
+            scroll: 0,
+            level: LogKind::Debug,
+        }
+    }
+}
+
+impl LogView {
+    pub fn up(&mut self, n: usize) {
+        let len = logs::entries(&self.level).len();
+        self.scroll = (self.scroll + n).min(len.saturating_sub(1));
+    }
+
+    pub fn down(&mut self, n: usize) {
+        self.scroll = self.scroll.saturating_sub(n);
+    }
+
+    pub fn next_level(&mut self) {
+        self.level = self.level.next();
+        self.scroll = 0;
+    }
 }
 
 #[derive(Clone)]
@@ -104,70 +223,112 @@ pub enum Games {
 }
 
 impl App {
-    pub fn default() -> App {
+    pub fn new(client: Arc<WatcherClient>, config: &Config, tx: Sender) -> App {
         let map = routes();
-        let routes = RouteList {
+        let route = config.default_route();
+        let mut routes = RouteList {
             state: ListState::default(),
             items: map,
         };
+        routes
+            .state
+            .select(routes.items.iter().position(|f| f.1 == route));
 
-        let keys = Keys::default();
+        // bad bindings already failed in Config::check
+        let keys = Keys::new(&config.keys).unwrap_or_default();
 
         App {
             keys,
-            log: Log::new(crate::utils::LogKind::Info, "App start".into()), 
-            api_key: "".to_string(),// api key
+            profiles: ProfileList {
+                state: ListState::default(),
+                items: config.profiles().unwrap_or_default(),
+            },
+            log: logs::record(LogKind::Info, client.api_key.to_string()),
+            client,
             state: State::Idle,
             msg: None,
             focus: Some(Window::List),
             input: Input {
                 content: "".to_string(),
             },
-            route: PlatformRoute::KR,
+            route,
             routes,
+            full_scoreboard: false,
+            loads: Loads::default(),
+            popup: None,
+            log_view: LogView::default(),
+            palette: Palette::default(),
+            tick: 0,
+            search: 0,
+            refresh_live: false,
+            tx,
+            tasks: vec![],
             data: Data {
                 rank: None,
                 current_search: None,
-                env_search: None,
+                search_route: None,
+                search_name: None,
                 masteries: None,
                 summoner: None,
                 games: Games::N,
+                live: None,
+                live_checked: false,
+                timeline: None,
+                summary: None,
             },
         }
     }
 
-    pub async fn msg(&mut self) {
-        let msg = self.msg.clone();
+    pub fn msg(&mut self) {
+        let msg = self.msg.take();
 
         match msg {
             Some(msg) => match msg {
                 Msg::Quit => {}
                 Msg::Focus(w) => {
                     self.focus = Some(w);
-                    self.msg = None
+                    if w == Window::Live && !self.data.live_checked {
+                        self.load_live();
+                    }
                 }
                 Msg::Search(route, name) => {
                     self.input.clear();
-                    self.search_all(&route, &name).await;
-                    self.log = Log::new(crate::utils::LogKind::Info, "search finished".into())
+                    self.search(route, &name);
                 }
+                Msg::LoadMore => self.load_more(),
+                Msg::Timeline => self.toggle_timeline(),
+                Msg::Cancel => self.cancel(),
                 _ => {}
             },
             None => {}
         }
     }
 
-    pub fn get_env_search(&mut self) -> Result<(), Error> {
-        let name: Option<&str> = std::option_env!("WATCHER_NAME");
-        let region: Option<&str> = std::option_env!("WATCHER_REGION");
-        if let Some(name) = name {
-            if let Some(region) = region {
-                self.data.env_search = Some((name.into(), region.into()));
-                return Ok(());
-            }
-            return Err(Error::default());
+    pub fn busy(&self) -> bool {
+        self.loads.busy()
+    }
+
+    /// runs `task` in the background, its result comes back through `update`
+    fn spawn<F>(&mut self, task: F)
+    where
+        F: Future<Output = Update> + Send + 'static,
+    {
+        self.tasks.retain(|t| !t.is_finished());
+        let (tx, search) = (self.tx.clone(), self.search);
+        self.tasks.push(tokio::spawn(async move {
+            let _ = tx.send((search, task.await));
+        }));
+    }
+
+    /// Esc while loading, what already arrived stays
+    pub fn cancel(&mut self) {
+        for task in self.tasks.drain(..) {
+            task.abort();
         }
-        Err(Error::default())
+        self.search += 1;
+        self.loads.cancel();
+        self.state = State::Idle;
+        self.log = logs::record(LogKind::Warning, "search cancelled".into());
     }
 
     pub fn up(&mut self) {
@@ -183,10 +344,15 @@ impl App {
             _ => {}
         }
     }
-    pub fn down(&mut self) {
+    pub fn down(&mut self) -> Option<Msg> {
         match self.focus.unwrap_or(Window::Header) {
             Window::List => match self.data.games {
-                Games::G(ref mut g) => g.next(),
+                Games::G(ref mut g) => {
+                    if g.more && g.state.selected() == Some(g.items.len().saturating_sub(1)) {
+                        return Some(Msg::LoadMore);
+                    }
+                    g.next()
+                }
                 Games::N => {}
             },
             Window::Route => {
@@ -195,88 +361,375 @@ impl App {
             }
             _ => {}
         }
+        None
     }
 
     pub fn enter(&mut self) -> Msg {
         match self.focus.unwrap_or(Window::Header) {
             Window::Route => Msg::Search(self.route, self.input.clone().get()),
+            Window::Profiles => match self.profiles.get_item() {
+                Some(p) => {
+                    self.log = logs::record(LogKind::Info, format!("searching: @{} {}", p.name, p.riot_id));
+                    let msg = Msg::Search(p.route.unwrap_or(self.route), p.riot_id.clone());
+                    self.focus = Some(Window::List);
+                    msg
+                }
+                None => Msg::Focus(Window::List),
+            },
             _ => Msg::None,
         }
     }
 
-    pub fn into_route(mut self, text: String) -> PlatformRoute {
-        self.routes.get_item(Some(text))
-    }
-
-    async fn search_all(&mut self, route: &PlatformRoute, name: &str) {
-        let name = name.replace(" ", "");
-        let puuid: &str;
-        let res = get_summoner(&self.api_key, *route, &name).await.unwrap_or(None);
-        match res {
-            None => self.state = State::Failed(name.to_string(), *route),
-
-            Some(sumoner) => {
-                self.state = State::Searching(name.to_string(), *route);
-                puuid = &sumoner.puuid;
-                self.data.summoner = Some(SummonerDisplay::with(sumoner.clone()));
-                self.data.current_search = Some((sumoner.id, sumoner.name));
-
-                let res = get_rank(
-                    &self.api_key,
-                    *route,
-                    &self.data.current_search.as_ref().unwrap().0,
-                )
-                .await;
-                let entry: Option<Vec<LeagueEntryDisplay>> = match res {
-                    Err(_) => None,
-                    Ok(rank) => Some(
-                        rank.iter()
-                            .map(|f| LeagueEntryDisplay::with(f.clone()))
-                            .collect(),
-                    ),
-                };
-                self.data.rank = entry;
-
-                let res = get_masteries(
-                    &self.api_key,
-                    *route,
-                    &self.data.current_search.as_ref().unwrap().0,
-                    10,
-                )
-                .await;
-                let entry: Option<Vec<ChampionMasteryDisplay>> = match res {
-                    Err(_) => None,
-                    Ok(m) => Some(
-                        m.iter()
-                            .map(|f| ChampionMasteryDisplay::with(f.clone()))
-                            .collect(),
-                    ),
-                };
-                self.data.masteries = entry;
+    /// a command from the palette
+    pub fn run(&mut self, command: Command) -> Option<Msg> {
+        match command {
+            Command::Search(name, route) => {
+                if let Some(route) = route {
+                    self.set_route(route);
+                }
+                self.log = logs::record(LogKind::Info, format!("searching: {} {}", self.route, name));
+                Some(Msg::Search(self.route, name))
+            }
+            Command::Region(route) => {
+                self.set_route(route);
+                self.log = logs::record(LogKind::Info, format!("region {}", route));
+                None
+            }
+            Command::Export(format) => {
+                self.export(format);
+                None
+            }
+            Command::Refresh => self.refresh(),
+            Command::Quit => Some(Msg::Quit),
+        }
+    }
+
+    fn set_route(&mut self, route: PlatformRoute) {
+        self.route = route;
+        let i = self.routes.items.iter().position(|f| f.1 == route);
+        self.routes.state.select(i);
+    }
+
+    /// the loaded games like `watcher games --format <format>`, into the current dir
+    fn export(&mut self, format: Format) {
+        let (player, route) = match (&self.data.current_search, self.data.search_route, &self.data.games) {
+            (Some(player), Some(route), Games::G(_)) => (player, route),
+            _ => {
+                self.log = logs::record(LogKind::Warning, "no games to export".into());
+                return;
+            }
+        };
+        let ext = match format {
+            Format::Ndjson => "ndjson",
+            Format::Csv => "csv",
+            Format::Tsv => "tsv",
+            _ => "json",
+        };
+        let name = player.name().replace(|c: char| !c.is_alphanumeric(), "_");
+        let path = format!("watcher-{}-{}.{}", name, chrono::Local::now().format("%Y%m%d-%H%M%S"), ext);
+        let file = match File::create(&path) {
+            Ok(file) => file,
+            Err(e) => {
+                self.log = logs::record(LogKind::Error, format!("couldn't create {}: {}", path, e));
+                return;
+            }
+        };
+        let mut out = Output::with_sink(format, Box::new(BufWriter::new(file)));
+        let mut count = 0;
+        if let Games::G(g) = &self.data.games {
+            for game in &g.items {
+                let data = GameJson::new(&game.0, &player.summoner.puuid);
+                out.emit("game", &player.name(), &route.to_string(), data, "");
+                count += 1;
+            }
+        }
+        out.finish();
+        self.log = logs::record(LogKind::Info, format!("exported {} games to {}", count, path));
+    }
+
+    /// the current player again, cached summoner, rank and masteries are dropped first
+    fn refresh(&mut self) -> Option<Msg> {
+        let (player, route, name) = match (&self.data.current_search, self.data.search_route, &self.data.search_name) {
+            (Some(player), Some(route), Some(name)) => (player, route, name.clone()),
+            _ => {
+                self.log = logs::record(LogKind::Warning, "nothing to refresh".into());
+                return None;
+            }
+        };
+        let cache = &self.client.cache;
+        cache.remove(Kind::Summoner, &format!("{}-{}", route, name));
+        cache.remove(Kind::Rank, &format!("{}-{}", route, player.summoner.id));
+        cache.remove(Kind::Mastery, &format!("{}-{}-{}", route, player.summoner.id, MASTERIES));
+        self.log = logs::record(LogKind::Info, format!("refreshing {} {}", route, name));
+        // a live game that was looked at is fetched again with the rest
+        self.refresh_live = self.data.live_checked || self.loads.live == Load::Loading;
+        Some(Msg::Search(route, name))
+    }
+
+    fn load_more(&mut self) {
+        if self.loads.games == Load::Loading {
+            return;
+        }
+        let (route, puuid) = match (self.data.search_route, &self.data.current_search) {
+            (Some(route), Some(player)) => (route, player.summoner.puuid.clone()),
+            _ => return,
+        };
+        let query = match &self.data.games {
+            Games::G(g) if g.more => g.query.next_page(),
+            _ => return,
+        };
+        self.log = logs::record(
+            LogKind::Info,
+            format!("loading games {}-{}", query.start, query.start + query.count),
+        );
+        self.loads.games = Load::Loading;
+        let client = self.client.clone();
+        self.spawn(async move {
+            let res = get_games(&client, route, &puuid, &query).await;
+            Update::MoreGames(query, res)
+        });
+    }
 
-                let res = get_games(&self.api_key, *route, puuid).await;
-                let entry: Option<Vec<MatchDisplay>> = match res {
-                    Err(_) => None,
-                    Ok(rank) => Some(rank.iter().map(|f| MatchDisplay::with(f.clone())).collect()),
+    fn update_summary(&mut self) {
+        self.data.summary = match (&self.data.games, &self.data.current_search) {
+            (Games::G(g), Some(player)) => {
+                let games = g.items.iter().map(|m| m.0.clone()).collect::<Vec<_>>();
+                Some(SummaryDisplay::with(Summary::new(&games, &player.summoner.puuid)))
+            }
+            _ => None,
+        };
+    }
+
+    fn toggle_timeline(&mut self) {
+        let (route, game) = match (self.data.search_route, &self.data.games) {
+            (Some(route), Games::G(g)) => match g.items.get(g.state.selected().unwrap_or(0)) {
+                Some(game) => (route, game.0.clone()),
+                None => return,
+            },
+            _ => return,
+        };
+        let id = game.metadata.match_id.clone();
+        if self.data.timeline.as_ref().map(|t| t.match_id == id).unwrap_or(false) {
+            self.data.timeline = None;
+            return;
+        }
+        self.log = logs::record(LogKind::Info, format!("loading timeline of {}", id));
+        self.loads.timeline = Load::Loading;
+        self.focus = Some(Window::Games);
+        let client = self.client.clone();
+        self.spawn(async move { Update::Timeline(get_timeline(&client, route, &game).await) });
+    }
+
+    /// logs a failed panel, errors that hit every request also get a popup
+    fn check<T>(&mut self, panel: &str, res: &Result<T, WatcherError>) -> Load {
+        if let Err(e) = res {
+            self.log = logs::record(LogKind::Error, format!("{} failed: {}", panel, e));
+            if self.popup.is_none() && WatcherErr::global(e) {
+                self.popup = Some(WatcherErr::Riot(e.clone()));
+            }
+        }
+        Load::from(res)
+    }
+
+    fn log_failed(&mut self, failed: &[(String, WatcherError)]) {
+        if failed.is_empty() {
+            return;
+        }
+        self.log = logs::record(
+            LogKind::Warning,
+            format!(
+                "{} games failed: {}",
+                failed.len(),
+                failed
+                    .iter()
+                    .map(|(id, reason)| format!("{} ({})", id, reason))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        );
+    }
+
+    /// drops the last search and starts with the summoner, the rest follows in `update`
+    fn search(&mut self, route: PlatformRoute, name: &str) {
+        let name = match RiotId::parse(name) {
+            Some(riot_id) => riot_id.to_string(),
+            None => name.replace(' ', ""),
+        };
+        for task in self.tasks.drain(..) {
+            task.abort();
+        }
+        self.search += 1;
+        self.popup = None;
+        self.loads = Loads {
+            summoner: Load::Loading,
+            ..Loads::default()
+        };
+        self.state = State::Searching(name.clone(), route);
+        self.data.search_route = Some(route);
+        self.data.search_name = Some(name.clone());
+
+        let client = self.client.clone();
+        self.spawn(async move { Update::Summoner(get_summoner(&client, route, &name).await) });
+    }
+
+    /// a finished task, the rest of the search starts once the summoner is known
+    pub fn update(&mut self, (search, update): Tagged) {
+        if search != self.search {
+            return;
+        }
+        let route = match self.data.search_route {
+            Some(route) => route,
+            None => return,
+        };
+        match update {
+            Update::Summoner(res) => {
+                self.loads.summoner = Load::from(&res);
+                let name = match &self.state {
+                    State::Searching(name, _) => name.clone(),
+                    _ => String::new(),
                 };
-                match entry {
-                    Some(e) => self.data.games = Games::G(GamesList::with(e)),
-                    None => self.data.games = Games::N,
+                match res {
+                    Ok(Some(player)) => self.found(route, player),
+                    Ok(None) | Err(WatcherError::NotFound(_)) => {
+                        self.log = logs::record(LogKind::Error, format!("{} not found on {}", name, route));
+                        self.popup = Some(WatcherErr::SearchFalied { name: name.clone(), route });
+                        self.state = State::Failed(name, route);
+                    }
+                    Err(e) => {
+                        let hint = self.client.api_key.expired_hint(&e).unwrap_or_else(|| e.to_string());
+                        self.log = logs::record(LogKind::Error, hint);
+                        self.popup = Some(WatcherErr::Riot(e.clone()));
+                        self.state = State::Error(WatcherErr::Riot(e));
+                    }
                 }
+            }
+            Update::Rank(res) => {
+                self.loads.rank = self.check("rank", &res);
+                let name = self.data.current_search.as_ref().map(|p| p.name()).unwrap_or_default();
+                self.data.rank = res.ok().map(|rank| {
+                    rank.into_iter()
+                        .map(|f| LeagueEntryDisplay::with(LeagueEntry { summoner_name: name.clone(), ..f }))
+                        .collect()
+                });
+            }
+            Update::Masteries(res) => {
+                self.loads.masteries = self.check("masteries", &res);
+                self.data.masteries = res
+                    .ok()
+                    .map(|m| m.into_iter().map(ChampionMasteryDisplay::with).collect());
+            }
+            Update::Games(query, res) => {
+                self.loads.games = self.check("games", &res);
+                match res {
+                    Err(_) => self.data.games = Games::N,
+                    Ok(games) => {
+                        self.log_failed(&games.failed);
+                        let mut list = GamesList::with(
+                            games.matches.into_iter().map(MatchDisplay::with).collect(),
+                        );
+                        list.query = query;
+                        list.more = games.more;
+                        self.data.games = Games::G(list);
+                    }
+                }
+                self.update_summary();
+            }
+            Update::MoreGames(query, res) => {
+                self.loads.games = self.check("games", &res);
+                if let Ok(games) = res {
+                    self.log_failed(&games.failed);
+                    if let Games::G(ref mut g) = self.data.games {
+                        g.items
+                            .extend(games.matches.into_iter().map(MatchDisplay::with));
+                        g.query = query;
+                        g.more = games.more;
+                        g.next();
+                    }
+                    self.update_summary();
+                }
+            }
+            Update::Live(res) => {
+                self.loads.live = self.check("live game", &res);
+                // only now, a cancelled fetch leaves it unchecked
+                self.data.live_checked = true;
+                self.data.live = res.ok().flatten().map(LiveGameDisplay::with);
+            }
+            Update::Timeline(res) => {
+                self.loads.timeline = self.check("timeline", &res);
+                if let Ok(timeline) = res {
+                    self.data.timeline = Some(timeline);
+                }
+            }
+        }
 
-                self.msg = None;
-
-                self.focus = Some(Window::List);
-                self.state = State::Idle
+        if let (State::Searching(..), false) = (&self.state, self.busy()) {
+            self.state = State::Idle;
+            if matches!(self.log.kind, LogKind::Info) {
+                self.log = logs::record(LogKind::Info, "search finished".into())
             }
         }
     }
+
+    /// the summoner is known, every panel loads at once
+    fn found(&mut self, route: PlatformRoute, player: Player) {
+        let (id, puuid) = (player.summoner.id.clone(), player.summoner.puuid.clone());
+        recent::push(&player.name(), route);
+        self.data.summoner = Some(SummonerDisplay::with(player.clone()));
+        self.data.current_search = Some(player);
+        self.data.rank = None;
+        self.data.masteries = None;
+        self.data.games = Games::N;
+        self.data.live = None;
+        self.data.live_checked = false;
+        self.data.timeline = None;
+        self.data.summary = None;
+        self.focus = Some(Window::List);
+        self.loads.rank = Load::Loading;
+        self.loads.masteries = Load::Loading;
+        self.loads.games = Load::Loading;
+
+        let client = self.client.clone();
+        let summoner_id = id.clone();
+        self.spawn(async move { Update::Rank(get_rank(&client, route, &summoner_id).await) });
+
+        let client = self.client.clone();
+        self.spawn(async move { Update::Masteries(get_masteries(&client, route, &id, MASTERIES).await) });
+
+        let client = self.client.clone();
+        let games_puuid = puuid.clone();
+        self.spawn(async move {
+            let query = MatchQuery::default();
+            let res = get_games(&client, route, &games_puuid, &query).await;
+            Update::Games(query, res)
+        });
+
+        if self.refresh_live {
+            self.refresh_live = false;
+            self.load_live();
+        }
+    }
+
+    /// one spectator call plus a rank per player, so only once the live panel is opened
+    fn load_live(&mut self) {
+        let (route, puuid) = match (self.data.search_route, &self.data.current_search) {
+            (Some(route), Some(player)) => (route, player.summoner.puuid.clone()),
+            _ => return,
+        };
+        if self.loads.live == Load::Loading {
+            return;
+        }
+        self.loads.live = Load::Loading;
+        let client = self.client.clone();
+        self.spawn(async move { Update::Live(get_live_game(&client, route, &puuid).await) });
+    }
 }
 
 #[derive(Clone)]
 pub struct GamesList {
     pub state: ListState,
     pub items: Vec<MatchDisplay>,
+    pub query: MatchQuery,
+    pub more: bool,
 }
 
 
@@ -286,6 +739,8 @@ impl GamesList {
         GamesList {
             state: ListState::default(),
             items,
+            query: MatchQuery::default(),
+            more: false,
         }
     }
 
@@ -293,6 +748,8 @@ impl GamesList {
         GamesList {
             state: ListState::default(),
             items,
+            query: MatchQuery::default(),
+            more: false,
         }
     }
 
@@ -457,3 +914,38 @@ impl RouteList {
         }
     }
 }
+
+/// `[profiles]` from the config, picked with `f`
+#[derive(Clone)]
+pub struct ProfileList {
+    pub state: ListState,
+    pub items: Vec<Profile>,
+}
+
+impl ProfileList {
+    pub fn next(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(i) if i + 1 < self.items.len() => i + 1,
+            _ => 0,
+        };
+        self.state.select(Some(i));
+    }
+
+    pub fn previous(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
+        let i = match self.state.selected() {
+            Some(0) | None => self.items.len() - 1,
+            Some(i) => i - 1,
+        };
+        self.state.select(Some(i));
+    }
+
+    pub fn get_item(&self) -> Option<&Profile> {
+        self.items.get(self.state.selected().unwrap_or(0))
+    }
+}
@@ -1,121 +1,521 @@
-use std::{fmt::Display, io, time::Duration};
+use std::{collections::BTreeMap, fmt::Display, io, time::Duration};
 
 use crossterm::{
     event::{self, Event, KeyCode},
     style::Stylize,
 };
 
-use crate::utils::Log;
+use crate::logs;
 
-use super::app::{App, Msg, Window};
+use super::{
+    app::{App, Msg, Window},
+    palette::{Command, Palette},
+};
+
+/// everything a key can do, `[keys]` in config.toml uses the snake_case names
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    /// cancels a search in flight, quits otherwise
+    Cancel,
+    FocusInput,
+    FocusRoute,
+    FocusList,
+    NextWindow,
+    Down,
+    Up,
+    /// search from the input, select everywhere else
+    Enter,
+    Paste,
+    ClearInput,
+    Profiles,
+    Live,
+    Timeline,
+    FullScoreboard,
+    Summary,
+    Logs,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    Help,
+    Palette,
+}
+
+impl Action {
+    pub const ALL: [Action; 23] = [
+        Action::Quit,
+        Action::Cancel,
+        Action::FocusInput,
+        Action::FocusRoute,
+        Action::FocusList,
+        Action::NextWindow,
+        Action::Down,
+        Action::Up,
+        Action::Enter,
+        Action::Paste,
+        Action::ClearInput,
+        Action::Profiles,
+        Action::Live,
+        Action::Timeline,
+        Action::FullScoreboard,
+        Action::Summary,
+        Action::Logs,
+        Action::PageUp,
+        Action::PageDown,
+        Action::Top,
+        Action::Bottom,
+        Action::Help,
+        Action::Palette,
+    ];
+
+    /// the rest is in the help overlay
+    pub const FOOTER: [Action; 7] = [
+        Action::Help,
+        Action::Palette,
+        Action::FocusInput,
+        Action::NextWindow,
+        Action::Enter,
+        Action::Cancel,
+        Action::Quit,
+    ];
+
+    /// help overlay sections, an action can be in more than one
+    pub const GROUPS: [(&'static str, &'static [Action]); 5] = [
+        (
+            "everywhere",
+            &[
+                Action::Quit,
+                Action::Cancel,
+                Action::NextWindow,
+                Action::FocusInput,
+                Action::FocusRoute,
+                Action::FocusList,
+                Action::Profiles,
+                Action::Logs,
+                Action::Help,
+                Action::Palette,
+            ],
+        ),
+        ("input", &[Action::Enter, Action::Paste, Action::ClearInput]),
+        (
+            "games",
+            &[
+                Action::Down,
+                Action::Up,
+                Action::Timeline,
+                Action::FullScoreboard,
+                Action::Live,
+                Action::Summary,
+            ],
+        ),
+        ("region", &[Action::Down, Action::Up, Action::Enter]),
+        (
+            "logs",
+            &[
+                Action::Up,
+                Action::Down,
+                Action::PageUp,
+                Action::PageDown,
+                Action::Top,
+                Action::Bottom,
+                Action::NextWindow,
+            ],
+        ),
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Cancel => "cancel",
+            Action::FocusInput => "focus_input",
+            Action::FocusRoute => "focus_route",
+            Action::FocusList => "focus_list",
+            Action::NextWindow => "next_window",
+            Action::Down => "down",
+            Action::Up => "up",
+            Action::Enter => "enter",
+            Action::Paste => "paste",
+            Action::ClearInput => "clear_input",
+            Action::Profiles => "profiles",
+            Action::Live => "live",
+            Action::Timeline => "timeline",
+            Action::FullScoreboard => "full_scoreboard",
+            Action::Summary => "summary",
+            Action::Logs => "logs",
+            Action::PageUp => "page_up",
+            Action::PageDown => "page_down",
+            Action::Top => "top",
+            Action::Bottom => "bottom",
+            Action::Help => "help",
+            Action::Palette => "palette",
+        }
+    }
+
+    /// shown in the footer and the help
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Cancel => "cancel search/quit",
+            Action::FocusInput => "focus input",
+            Action::FocusRoute => "region",
+            Action::FocusList => "games",
+            Action::NextWindow => "switch window",
+            Action::Down => "down",
+            Action::Up => "up",
+            Action::Enter => "search/select",
+            Action::Paste => "clipboard",
+            Action::ClearInput => "clear input",
+            Action::Profiles => "profiles",
+            Action::Live => "live game",
+            Action::Timeline => "timeline",
+            Action::FullScoreboard => "full scoreboard",
+            Action::Summary => "summary",
+            Action::Logs => "logs",
+            Action::PageUp => "page up",
+            Action::PageDown => "page down",
+            Action::Top => "top",
+            Action::Bottom => "bottom",
+            Action::Help => "help",
+            Action::Palette => "commands",
+        }
+    }
+
+    fn default_keys(&self) -> Vec<KeyCode> {
+        match self {
+            Action::Quit => vec![KeyCode::Char('q')],
+            Action::Cancel => vec![KeyCode::Esc],
+            Action::FocusInput => vec![KeyCode::Char('i')],
+            Action::FocusRoute => vec![KeyCode::Char('r')],
+            Action::FocusList => vec![KeyCode::Char('l')],
+            Action::NextWindow => vec![KeyCode::Tab],
+            Action::Down => vec![KeyCode::Down, KeyCode::Char('j')],
+            Action::Up => vec![KeyCode::Up, KeyCode::Char('k')],
+            Action::Enter => vec![KeyCode::Enter],
+            Action::Paste => vec![KeyCode::Insert],
+            Action::ClearInput => vec![KeyCode::Delete],
+            Action::Profiles => vec![KeyCode::Char('f')],
+            Action::Live => vec![KeyCode::Char('v')],
+            Action::Timeline => vec![KeyCode::Char('t')],
+            Action::FullScoreboard => vec![KeyCode::Char('s')],
+            Action::Summary => vec![KeyCode::Char('u')],
+            Action::Logs => vec![KeyCode::Char('L')],
+            Action::PageUp => vec![KeyCode::PageUp],
+            Action::PageDown => vec![KeyCode::PageDown],
+            Action::Top => vec![KeyCode::Home],
+            Action::Bottom => vec![KeyCode::End],
+            Action::Help => vec![KeyCode::Char('?')],
+            Action::Palette => vec![KeyCode::Char(':')],
+        }
+    }
+
+    fn parse(name: &str) -> Option<Action> {
+        Action::ALL.into_iter().find(|a| a.name() == name)
+    }
+}
 
-#[derive(Clone)]
+/// a single char, or Esc, Enter, Tab, Up, Down, Left, Right, Insert, Delete,
+/// Backspace, Home, End, PageUp, PageDown, Space and F1-F12 in any case
+pub fn parse_key(key: &str) -> Option<KeyCode> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    let code = match key.to_lowercase().as_str() {
+        "esc" => KeyCode::Esc,
+        "enter" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "insert" => KeyCode::Insert,
+        "delete" => KeyCode::Delete,
+        "backspace" => KeyCode::Backspace,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "space" => KeyCode::Char(' '),
+        f => KeyCode::F(f.strip_prefix('f')?.parse().ok().filter(|n| (1..=12).contains(n))?),
+    };
+    Some(code)
+}
+
+fn key_name(key: &KeyCode) -> String {
+    match key {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        _ => format!("{:?}", key),
+    }
+}
+
+/// every action with its keys, the defaults with `[keys]` from config.toml on top
+#[derive(Debug, Clone)]
 pub struct Keys {
-    pub keys: Vec<(Vec<KeyCode>, String)>,
+    pub keys: Vec<(Action, Vec<KeyCode>)>,
 }
 
 impl Keys {
-    #[allow(dead_code)]
-    pub fn with(keys: Vec<(Vec<KeyCode>, String)>) -> Keys {
-        Keys { keys }
+    /// `overrides` replace the default keys of their action, a key bound twice is an error
+    pub fn new(overrides: &BTreeMap<String, Vec<String>>) -> Result<Keys, String> {
+        let mut keys = Keys::default();
+        for (name, bound) in overrides {
+            let action = Action::parse(name).ok_or_else(|| format!("[keys] unknown action {}", name))?;
+            let codes = bound
+                .iter()
+                .map(|k| parse_key(k).ok_or_else(|| format!("[keys] unknown key {:?} for {}", k, name)))
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some(entry) = keys.keys.iter_mut().find(|(a, _)| *a == action) {
+                entry.1 = codes;
+            }
+        }
+        keys.check()?;
+        Ok(keys)
+    }
+
+    fn check(&self) -> Result<(), String> {
+        for (i, (action, codes)) in self.keys.iter().enumerate() {
+            for code in codes {
+                if let Some((other, _)) = self.keys[i + 1..].iter().find(|(_, c)| c.contains(code)) {
+                    return Err(format!(
+                        "[keys] {} is bound to both {} and {}",
+                        key_name(code),
+                        action.name(),
+                        other.name()
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// `j/Down`, empty when unbound
+    pub fn of(&self, action: Action) -> String {
+        self.keys
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, codes)| codes.iter().map(key_name).collect::<Vec<_>>().join("/"))
+            .unwrap_or_default()
+    }
+
+    pub fn action(&self, code: KeyCode) -> Option<Action> {
+        self.keys
+            .iter()
+            .find(|(_, codes)| codes.contains(&code))
+            .map(|(action, _)| *action)
     }
 }
 
 impl Default for Keys {
     fn default() -> Self {
-        let keys: Vec<(Vec<KeyCode>, String)> = vec![
-            (vec![KeyCode::Char('q'), KeyCode::Esc], "Quit".into()),
-            (vec![KeyCode::Char('i')], "focus input".into()),
-            (vec![KeyCode::Tab], "switch window".into()),
-            (vec![KeyCode::Down, KeyCode::Char('j')], "down".into()),
-            (vec![KeyCode::Up, KeyCode::Char('j')], "up".into()),
-            (vec![KeyCode::Enter], "search".into()),
-            (vec![KeyCode::Insert], "clipboard".into()),
-            (vec![KeyCode::Delete], "clear input".into()),
-            (vec![KeyCode::Char('f')], "search with ENV vars".into()),
-        ];
-        Keys { keys }
+        Keys {
+            keys: Action::ALL.iter().map(|a| (*a, a.default_keys())).collect(),
+        }
     }
 }
 
+/// the footer, `Action::FOOTER` only
 impl Display for Keys {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let text = self.keys.iter().map(|f| {
-            let k =
-                f.0.iter()
-                    .map(|f| format!("{:?}", {
-                        match  f {
-                            KeyCode::Char(c) => format!("{c}"),
-                            _ => format!("{:?}", f)
-                        }}).with(crossterm::style::Color::Green).to_string())
+        let text = self
+            .keys
+            .iter()
+            .filter(|(action, codes)| !codes.is_empty() && Action::FOOTER.contains(action))
+            .map(|(action, codes)| {
+                let k = codes
+                    .iter()
+                    .map(|c| key_name(c).with(crossterm::style::Color::Green).to_string())
                     .collect::<Vec<String>>()
                     .join(&"/".with(crossterm::style::Color::Reset).to_string());
-           format!("{} -> {}", k, f.1.clone().with(crossterm::style::Color::Yellow).to_string()) 
-        }).collect::<Vec<String>>().join(" | ");
+                format!("{} -> {}", k, action.description().with(crossterm::style::Color::Yellow))
+            })
+            .collect::<Vec<String>>()
+            .join(" | ");
         write!(f, "{}", text)
     }
 }
 
 pub async fn handle_keys(timeout: Duration, app: &mut App) -> io::Result<Option<Msg>> {
-    if crossterm::event::poll(timeout)? {
-        if app.focus.unwrap_or(super::app::Window::Header) != Window::Input {
-            if let Event::Key(key) = event::read()? {
-                match key.code {
-                    KeyCode::Char('q') => return Ok(Some(Msg::Quit)),
-                    KeyCode::Esc => return Ok(Some(Msg::Quit)),
-                    KeyCode::Enter => return Ok(Some(app.enter())),
-                    KeyCode::Char('f') => {
-                        let msg = match app.data.clone().env_search {
-                            None => match app.get_env_search() {
-                                Ok(..) => None,
-                                Err(..) => None,
-                            },
-                            Some(search) => {
-                                app.log = Log::new(crate::utils::LogKind::Info, 
-                                        format!("searching: {} {}", app.clone().into_route(search.1.clone()), search.0.clone()));
-                                Some(Msg::Search(app.clone().into_route(search.1), search.0))
-                            }
-                        };
-                        return Ok(msg);
-                    }
-                    KeyCode::Tab => {
-                        app.focus = Some(app.focus.unwrap_or(super::app::Window::Header).next())
-                    }
-                    KeyCode::Char('j') => app.down(),
-                    KeyCode::Down => app.down(),
-                    KeyCode::Up => app.up(),
-                    KeyCode::Char('k') => app.up(),
-                    KeyCode::Char('i') => return Ok(Some(Msg::Focus(super::app::Window::Input))),
-                    KeyCode::Char('r') => return Ok(Some(Msg::Focus(super::app::Window::Route))),
-                    KeyCode::Char('l') => return Ok(Some(Msg::Focus(super::app::Window::List))),
-                    _ => {}
-                }
+    if !crossterm::event::poll(timeout)? {
+        return Ok(None);
+    }
+    let code = match event::read()? {
+        Event::Key(key) => key.code,
+        _ => return Ok(None),
+    };
+    // any key closes the error popup
+    if app.popup.is_some() {
+        app.popup = None;
+        return Ok(None);
+    }
+    let action = app.keys.action(code);
+    let msg = match app.focus {
+        // any key closes the help
+        Some(Window::Help) => Some(Msg::Focus(Window::List)),
+        Some(Window::Palette) => match code {
+            KeyCode::Char(c) => {
+                app.palette.push(c);
+                None
+            }
+            KeyCode::Backspace if action.is_none() => {
+                app.palette.pop();
+                None
             }
+            _ => palette(app, action),
+        },
+        Some(Window::Logs) => log_view(app, action),
+        Some(Window::Profiles) => profiles(app, action),
+        Some(Window::Input) => match code {
+            // typing wins over bindings on chars
+            KeyCode::Char(c) => {
+                app.input.append(c.to_string());
+                None
+            }
+            KeyCode::Backspace if action.is_none() => {
+                app.input.delete();
+                None
+            }
+            _ => action.and_then(|a| dispatch(app, a)),
+        },
+        _ => action.and_then(|a| dispatch(app, a)),
+    };
+    Ok(msg)
+}
+
+fn dispatch(app: &mut App, action: Action) -> Option<Msg> {
+    match action {
+        Action::Quit => return Some(Msg::Quit),
+        Action::Cancel if app.busy() => return Some(Msg::Cancel),
+        Action::Cancel => return Some(Msg::Quit),
+        Action::Enter if app.focus == Some(Window::Input) => {
+            let name = app.input.clone().get();
+            app.log = logs::record(
+                crate::utils::LogKind::Info,
+                format!("searching: {} {}", app.route, name),
+            );
+            return Some(Msg::Search(app.route, name));
         }
-        if app.focus.unwrap_or(Window::Input) == Window::Input {
-            if let Event::Key(key) = event::read()? {
-                match key.code {
-                    KeyCode::Esc => return Ok(Some(Msg::Quit)),
-                    KeyCode::Tab => {
-                        app.focus = Some(app.focus.unwrap_or(super::app::Window::Header).next())
-                    }
-                    KeyCode::Enter => {
-                        let tmp = app.clone().input.get();
-                                app.log = Log::new(crate::utils::LogKind::Info, 
-                                        format!("searching: {} {}", app.route, tmp));
-                        return Ok(Some(Msg::Search(app.route, tmp)));
-                    }
-                    KeyCode::Char(c) => app.input.append(c.to_string()),
-                    KeyCode::Backspace => app.input.delete(),
-                    KeyCode::Insert => {
-                        let clip = cli_clipboard::get_contents().unwrap_or("".to_string());
-                        app.input.set(clip)
-                    }
-                    KeyCode::Delete => app.input.clear(),
-                    _ => {}
-                }
+        Action::Enter => return Some(app.enter()),
+        Action::Paste => {
+            let clip = cli_clipboard::get_contents().unwrap_or("".to_string());
+            app.input.set(clip)
+        }
+        Action::ClearInput => app.input.clear(),
+        Action::Profiles => {
+            if app.profiles.items.is_empty() {
+                app.log = logs::record(
+                    crate::utils::LogKind::Warning,
+                    "no [profiles] in config.toml".into(),
+                );
+                return None;
             }
+            return Some(Msg::Focus(Window::Profiles));
+        }
+        Action::NextWindow => app.focus = Some(app.focus.unwrap_or(Window::Header).next()),
+        Action::Down => return app.down(),
+        Action::Up => app.up(),
+        Action::FocusInput => return Some(Msg::Focus(Window::Input)),
+        Action::FocusRoute => return Some(Msg::Focus(Window::Route)),
+        Action::FocusList => return Some(Msg::Focus(Window::List)),
+        Action::Live => return Some(Msg::Focus(Window::Live)),
+        Action::Timeline => return Some(Msg::Timeline),
+        Action::FullScoreboard => app.full_scoreboard = !app.full_scoreboard,
+        Action::Summary => return Some(Msg::Focus(Window::Summary)),
+        Action::Logs => {
+            app.log_view.scroll = 0;
+            return Some(Msg::Focus(Window::Logs));
+        }
+        Action::Help => return Some(Msg::Focus(Window::Help)),
+        Action::Palette => {
+            app.palette = Palette::default();
+            return Some(Msg::Focus(Window::Palette));
         }
+        Action::PageUp | Action::PageDown | Action::Top | Action::Bottom => {}
+    }
+    None
+}
+
+/// Tab completes the command, Enter runs it, a bad one stays open with the error logged
+fn palette(app: &mut App, action: Option<Action>) -> Option<Msg> {
+    match action? {
+        Action::Cancel | Action::Quit => return Some(Msg::Focus(Window::List)),
+        Action::NextWindow => app.palette.complete(),
+        Action::Down => app.palette.next(),
+        Action::Up => app.palette.previous(),
+        Action::Enter => match Command::parse(&app.palette.input) {
+            Ok(command) => {
+                app.focus = Some(Window::List);
+                return app.run(command);
+            }
+            Err(e) => app.log = logs::record(crate::utils::LogKind::Error, e),
+        },
+        _ => {}
+    }
+    None
+}
+
+fn log_view(app: &mut App, action: Option<Action>) -> Option<Msg> {
+    match action? {
+        Action::Cancel | Action::Quit | Action::Logs => return Some(Msg::Focus(Window::List)),
+        Action::Up => app.log_view.up(1),
+        Action::Down => app.log_view.down(1),
+        Action::PageUp => app.log_view.up(10),
+        Action::PageDown => app.log_view.down(10),
+        Action::Top => app.log_view.up(usize::MAX / 2),
+        Action::Bottom => app.log_view.scroll = 0,
+        Action::NextWindow => app.log_view.next_level(),
+        _ => {}
+    }
+    None
+}
+
+fn profiles(app: &mut App, action: Option<Action>) -> Option<Msg> {
+    match action? {
+        Action::Cancel | Action::Quit | Action::Profiles => return Some(Msg::Focus(Window::List)),
+        Action::Enter => return Some(app.enter()),
+        Action::Down => app.profiles.next(),
+        Action::Up => app.profiles.previous(),
+        _ => {}
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crossterm::event::KeyCode;
+
+    use super::{parse_key, Action, Keys};
+
+    fn overrides(pairs: &[(&str, &[&str])]) -> BTreeMap<String, Vec<String>> {
+        pairs
+            .iter()
+            .map(|(a, keys)| (a.to_string(), keys.iter().map(|k| k.to_string()).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn defaults_have_no_conflicts() {
+        let keys = Keys::new(&BTreeMap::new()).unwrap();
+        assert_eq!(keys.action(KeyCode::Char('k')), Some(Action::Up));
+        assert_eq!(keys.action(KeyCode::Esc), Some(Action::Cancel));
+    }
+
+    #[test]
+    fn overrides_and_conflicts() {
+        let keys = Keys::new(&overrides(&[("quit", &["x", "F10"])])).unwrap();
+        assert_eq!(keys.action(KeyCode::Char('x')), Some(Action::Quit));
+        assert_eq!(keys.action(KeyCode::F(10)), Some(Action::Quit));
+        assert_eq!(keys.action(KeyCode::Char('q')), None);
+
+        let err = Keys::new(&overrides(&[("quit", &["j"])])).unwrap_err();
+        assert!(err.contains("quit") && err.contains("down"), "{}", err);
+        assert!(Keys::new(&overrides(&[("jump", &["x"])])).is_err());
+        assert!(Keys::new(&overrides(&[("quit", &["F13"])])).is_err());
+        assert_eq!(parse_key("pageup"), Some(KeyCode::PageUp));
+        assert_eq!(parse_key("Space"), Some(KeyCode::Char(' ')));
     }
-    Ok(None)
 }
@@ -1,3 +1,5 @@
 pub mod ui;
 pub mod app;
 pub mod keys;
+pub mod tasks;
+pub mod palette;
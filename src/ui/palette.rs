@@ -0,0 +1,178 @@
+//! `:` command palette, the first word is completed fuzzily
+
+use clap::ValueEnum;
+use riven::consts::PlatformRoute;
+
+use crate::{output::Format, utils::route_by_name};
+
+/// (name, usage)
+pub const COMMANDS: [(&str, &str); 5] = [
+    ("search", "search Name#TAG [region]"),
+    ("region", "region <region>, used by the next search"),
+    ("export", "export json|ndjson|csv|tsv, the loaded games to a file"),
+    ("refresh", "refresh, search the current player again skipping the cache"),
+    ("quit", "quit"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// without a region the selected one is used
+    Search(String, Option<PlatformRoute>),
+    Region(PlatformRoute),
+    Export(Format),
+    Refresh,
+    Quit,
+}
+
+impl Command {
+    /// the command name can be abbreviated as long as it matches one fuzzily
+    pub fn parse(input: &str) -> Result<Command, String> {
+        let input = input.trim().trim_start_matches(':');
+        let (word, rest) = match input.split_once(' ') {
+            Some((word, rest)) => (word, rest.trim()),
+            None => (input, ""),
+        };
+        let name = match complete(word).first() {
+            Some((name, _)) => *name,
+            None => return Err(format!("unknown command {}", word)),
+        };
+        match name {
+            "search" => {
+                let mut words = rest.split_whitespace().collect::<Vec<_>>();
+                let route = match words.last().and_then(|w| route_by_name(w)) {
+                    Some(route) if words.len() > 1 => {
+                        words.pop();
+                        Some(route)
+                    }
+                    _ => None,
+                };
+                if words.is_empty() {
+                    return Err("search needs a player".into());
+                }
+                Ok(Command::Search(words.join(" "), route))
+            }
+            "region" => route_by_name(rest)
+                .map(Command::Region)
+                .ok_or_else(|| format!("unknown region {:?}", rest)),
+            "export" => match Format::from_str(rest, true) {
+                Ok(Format::Text) | Err(_) => Err(format!("can't export as {:?}, use json, ndjson, csv or tsv", rest)),
+                Ok(format) => Ok(Command::Export(format)),
+            },
+            "refresh" => Ok(Command::Refresh),
+            _ => Ok(Command::Quit),
+        }
+    }
+}
+
+/// chars of `pattern` in order inside `candidate`, None when they aren't,
+/// higher is better, runs of consecutive chars and an early start count most, shorter candidates win ties
+pub fn fuzzy_score(pattern: &str, candidate: &str) -> Option<i32> {
+    let candidate = candidate.to_lowercase().chars().collect::<Vec<_>>();
+    let mut score = 0;
+    let mut next = 0;
+    let mut last: Option<usize> = None;
+    for c in pattern.to_lowercase().chars() {
+        let i = next + candidate[next..].iter().position(|x| *x == c)?;
+        score += match last {
+            Some(l) if l + 1 == i => 5,
+            _ if i == 0 => 10,
+            _ => 1,
+        };
+        last = Some(i);
+        next = i + 1;
+    }
+    Some(score - candidate.len() as i32)
+}
+
+/// commands matching `word`, best first, all of them when it's empty
+pub fn complete(word: &str) -> Vec<(&'static str, &'static str)> {
+    let mut matches = COMMANDS
+        .iter()
+        .filter_map(|(name, usage)| fuzzy_score(word, name).map(|score| (score, *name, *usage)))
+        .collect::<Vec<_>>();
+    // stable, ties keep the order of COMMANDS
+    matches.sort_by_key(|(score, _, _)| -score);
+    matches.into_iter().map(|(_, name, usage)| (name, usage)).collect()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Palette {
+    pub input: String,
+    /// index into `candidates`
+    pub selected: usize,
+}
+
+impl Palette {
+    pub fn candidates(&self) -> Vec<(&'static str, &'static str)> {
+        let word = self.input.split(' ').next().unwrap_or_default();
+        complete(word)
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.input.push(c);
+        self.selected = 0;
+    }
+
+    pub fn pop(&mut self) {
+        self.input.pop();
+        self.selected = 0;
+    }
+
+    pub fn next(&mut self) {
+        let len = self.candidates().len();
+        if len > 0 {
+            self.selected = (self.selected + 1) % len;
+        }
+    }
+
+    pub fn previous(&mut self) {
+        let len = self.candidates().len();
+        if len > 0 {
+            self.selected = (self.selected + len - 1) % len;
+        }
+    }
+
+    /// replaces the first word with the selected command
+    pub fn complete(&mut self) {
+        let name = match self.candidates().get(self.selected) {
+            Some((name, _)) => *name,
+            None => return,
+        };
+        let rest = self.input.split_once(' ').map(|(_, rest)| rest).unwrap_or_default();
+        self.input = format!("{} {}", name, rest);
+        self.selected = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use riven::consts::PlatformRoute;
+
+    use super::{complete, fuzzy_score, Command};
+    use crate::output::Format;
+
+    #[test]
+    fn fuzzy_completion() {
+        assert_eq!(complete("se")[0].0, "search");
+        assert_eq!(complete("rf")[0].0, "refresh");
+        assert_eq!(complete("rg")[0].0, "region");
+        assert_eq!(complete("").len(), 5);
+        assert!(complete("xyz").is_empty());
+        assert!(fuzzy_score("qt", "quit") > fuzzy_score("qt", "quitter"));
+    }
+
+    #[test]
+    fn parse_commands() {
+        assert_eq!(
+            Command::parse(":search Hide on bush#KR1 kr"),
+            Ok(Command::Search("Hide on bush#KR1".into(), Some(PlatformRoute::KR)))
+        );
+        assert_eq!(Command::parse("search euw"), Ok(Command::Search("euw".into(), None)));
+        assert_eq!(Command::parse("reg na"), Ok(Command::Region(PlatformRoute::NA1)));
+        assert_eq!(Command::parse("export CSV"), Ok(Command::Export(Format::Csv)));
+        assert!(Command::parse("export text").is_err());
+        assert_eq!(Command::parse("q"), Ok(Command::Quit));
+        assert!(Command::parse("search").is_err());
+        assert!(Command::parse("zzz").is_err());
+    }
+}
@@ -0,0 +1,134 @@
+//! searches run as tokio tasks and send what they got back to the ui loop
+
+use riven::models::{champion_mastery_v4::ChampionMastery, league_v4::LeagueEntry};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    api::{
+        api::{MatchList, MatchQuery},
+        player::Player,
+        spectator::LiveGame,
+        timeline::GameTimeline,
+    },
+    error::WatcherError,
+};
+
+use super::app::Window;
+
+pub const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// results of a task, the ui loop hands them to `App::update`
+pub enum Update {
+    Summoner(Result<Option<Player>, WatcherError>),
+    Rank(Result<Vec<LeagueEntry>, WatcherError>),
+    Masteries(Result<Vec<ChampionMastery>, WatcherError>),
+    /// first page of a search
+    Games(MatchQuery, Result<MatchList, WatcherError>),
+    /// next page, appended to the list
+    MoreGames(MatchQuery, Result<MatchList, WatcherError>),
+    Live(Result<Option<LiveGame>, WatcherError>),
+    Timeline(Result<GameTimeline, WatcherError>),
+}
+
+/// every update carries the search it belongs to, late ones from a cancelled search are dropped
+pub type Tagged = (u64, Update);
+pub type Sender = UnboundedSender<Tagged>;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Load {
+    #[default]
+    Idle,
+    Loading,
+    Failed(String),
+}
+
+/// one per panel
+#[derive(Debug, Clone, Default)]
+pub struct Loads {
+    pub summoner: Load,
+    pub rank: Load,
+    pub masteries: Load,
+    pub games: Load,
+    pub live: Load,
+    pub timeline: Load,
+}
+
+impl Loads {
+    pub fn busy(&self) -> bool {
+        self.all().iter().any(|(_, l)| **l == Load::Loading)
+    }
+
+    /// (panel, error) of every fetch that failed
+    pub fn failed(&self) -> Vec<(&'static str, &str)> {
+        self.all()
+            .into_iter()
+            .filter_map(|(name, l)| match l {
+                Load::Failed(e) => Some((name, e.as_str())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// the load drawn in the title of `window`
+    pub fn of(&self, window: Window) -> &Load {
+        match window {
+            Window::Header => &self.summoner,
+            Window::Rank => &self.rank,
+            Window::Masteries => &self.masteries,
+            Window::List | Window::Summary => &self.games,
+            Window::Games if self.timeline != Load::Idle => &self.timeline,
+            Window::Games => &self.games,
+            Window::Live => &self.live,
+            _ => &Load::Idle,
+        }
+    }
+
+    /// everything still loading stops, failures stay
+    pub fn cancel(&mut self) {
+        for load in self.all_mut() {
+            if *load == Load::Loading {
+                *load = Load::Idle;
+            }
+        }
+    }
+
+    fn all(&self) -> [(&'static str, &Load); 6] {
+        [
+            ("summoner", &self.summoner),
+            ("rank", &self.rank),
+            ("masteries", &self.masteries),
+            ("games", &self.games),
+            ("live", &self.live),
+            ("timeline", &self.timeline),
+        ]
+    }
+
+    fn all_mut(&mut self) -> [&mut Load; 6] {
+        [
+            &mut self.summoner,
+            &mut self.rank,
+            &mut self.masteries,
+            &mut self.games,
+            &mut self.live,
+            &mut self.timeline,
+        ]
+    }
+}
+
+impl Load {
+    /// block title, empty while idle so the panels look like before
+    pub fn title(&self, tick: usize) -> String {
+        match self {
+            Load::Idle => String::new(),
+            Load::Loading => format!("{} loading", SPINNER[tick % SPINNER.len()]),
+            Load::Failed(_) => "failed".to_string(),
+        }
+    }
+
+    pub fn from<T>(res: &Result<T, WatcherError>) -> Load {
+        match res {
+            Ok(_) => Load::Idle,
+            Err(e) => Load::Failed(e.to_string()),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
     io,
+    sync::Arc,
     ops::Index,
     thread,
     time::{Duration, Instant},
@@ -11,38 +12,48 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use riven::models::match_v5::Participant;
 
 use ratatui::{
     backend::{Backend, CrosstermBackend},
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    text::{Line, Text},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    text::{Line, Span, Text},
+    symbols,
+    widgets::{Axis, Block, Borders, Chart, Clear, Dataset, GraphType, List, ListItem, ListState, Paragraph, Wrap},
     Frame, Terminal,
 };
+use tokio::sync::mpsc;
 
 use crate::{
-    display::{border_color, concat_text, DisplayToText, MatchDisplay},
+    api::{
+        client::WatcherClient,
+        timeline::{EventKind, GameTimeline},
+    },
+    config::Config,
+    error::WatcherError,
+    logs,
+    utils::LogKind,
+    display::{border_color, concat_text, DisplayToText, MatchDisplay, ScoreboardDisplay, TimelineEventsDisplay, With},
     no_data,
 };
 
 use super::{
-    app::{App, Games, Msg},
-    keys::handle_keys,
+    app::{App, Games, Msg, State, WatcherErr, Window},
+    keys::{handle_keys, Action},
+    tasks::{Load, SPINNER},
 };
 
-pub async fn ui(api_key: &str) -> Result<(), io::Error> {
+pub async fn ui(client: Arc<WatcherClient>, config: &Config) -> Result<(), io::Error> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let tick_rate = Duration::from_millis(250);
-    let last_tick = Instant::now();
-    let mut app = App::default();
-    app.api_key = api_key.to_string();
+    let tick_rate = Duration::from_millis(100);
+    let mut last_tick = Instant::now();
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut app = App::new(client, config, tx);
 
     loop {
         let mut msg: Option<Msg> = None;
@@ -74,12 +85,18 @@ pub async fn ui(api_key: &str) -> Result<(), io::Error> {
             },
             Err(_) => {}
         }
-        let _ = app.get_env_search();
 
         app.msg = msg;
-        app.msg().await;
+        app.msg();
 
-        if last_tick.elapsed() >= tick_rate {}
+        while let Ok(update) = rx.try_recv() {
+            app.update(update);
+        }
+
+        if last_tick.elapsed() >= tick_rate {
+            app.tick = app.tick.wrapping_add(1);
+            last_tick = Instant::now();
+        }
     }
 }
 
@@ -91,6 +108,7 @@ fn draw<B: Backend>(f: &mut Frame<B>, app: &mut App) -> Option<Msg> {
             [
                 Constraint::Percentage(10),
                 Constraint::Percentage(80),
+                Constraint::Length(1),
                 Constraint::Percentage(10),
             ]
             .as_ref(),
@@ -99,6 +117,7 @@ fn draw<B: Backend>(f: &mut Frame<B>, app: &mut App) -> Option<Msg> {
 
     draw_header(f, app, chunks[0]);
     draw_conntent(f, app, chunks[1]);
+    draw_status(f, app, chunks[2]);
 
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
@@ -109,14 +128,242 @@ fn draw<B: Backend>(f: &mut Frame<B>, app: &mut App) -> Option<Msg> {
             ]
             .as_ref(),
         )
-        .split(chunks[2]);
+        .split(chunks[3]);
 
 
     draw_footer(f, app, chunks[0]);
     draw_logs(f, app, chunks[1]);
+
+    if app.focus == Some(Window::Profiles) {
+        draw_profiles(f, app, f.size());
+    }
+    if app.focus == Some(Window::Logs) {
+        draw_log_view(f, app, f.size());
+    }
+    if app.focus == Some(Window::Help) {
+        draw_help(f, app, f.size());
+    }
+    if app.focus == Some(Window::Palette) {
+        draw_palette(f, app, f.size());
+    }
+    if let Some(err) = app.popup.clone() {
+        draw_popup(f, app, &err, f.size());
+    }
     None
 }
 
+/// what the search is doing and which panels failed
+fn draw_status<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let mut spans = match &app.state {
+        State::Idle => match &app.data.current_search {
+            Some(player) => vec![Span::styled(
+                format!("{} on {}", player.name(), app.data.search_route.map(|r| r.to_string()).unwrap_or_default()),
+                Style::default().fg(Color::Green),
+            )],
+            None => vec![Span::styled("ready", Style::default().fg(Color::Gray))],
+        },
+        State::Searching(name, route) => vec![Span::styled(
+            format!("{} searching {} on {}", SPINNER[app.tick % SPINNER.len()], name, route),
+            Style::default().fg(Color::Yellow),
+        )],
+        State::Failed(name, route) => vec![Span::styled(
+            format!("{} not found on {}", name, route),
+            Style::default().fg(Color::Red),
+        )],
+        State::Error(err) => vec![Span::styled(err.to_string(), Style::default().fg(Color::Red))],
+    };
+    for (panel, err) in app.loads.failed() {
+        if panel == "summoner" {
+            continue;
+        }
+        spans.push(Span::raw(" | "));
+        spans.push(Span::styled(format!("{}: {}", panel, err), Style::default().fg(Color::Red)));
+    }
+    if app.busy() {
+        spans.push(Span::styled(" | Esc to cancel", Style::default().fg(Color::DarkGray)));
+    }
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// the whole history, newest at the bottom, Tab changes the lowest level shown
+fn draw_log_view<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let entries = logs::entries(&app.log_view.level);
+    let height = area.height.saturating_sub(2) as usize;
+    let end = entries.len().saturating_sub(app.log_view.scroll);
+    let start = end.saturating_sub(height);
+    let lines = entries[start..end]
+        .iter()
+        .map(|log| {
+            let color = match log.kind {
+                LogKind::Debug => Color::DarkGray,
+                LogKind::Info => Color::Green,
+                LogKind::Warning => Color::Yellow,
+                LogKind::Error => Color::Red,
+            };
+            Line::from(vec![
+                Span::styled(format!("{} ", log.time_stamp), Style::default().fg(Color::Magenta)),
+                Span::styled(format!("{:<8}", format!("{:?}", log.kind)), Style::default().fg(color)),
+                Span::raw(log.msg.clone()),
+            ])
+        })
+        .collect::<Vec<_>>();
+    let title = format!(
+        "logs >= {:?} {}/{} (j/k scroll, Tab level, Esc close)",
+        app.log_view.level,
+        end,
+        entries.len()
+    );
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// every action with its keys, grouped by where it works
+fn draw_help<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let area = centered(60, 80, area);
+    let mut lines = vec![];
+    for (group, actions) in Action::GROUPS {
+        lines.push(Line::from(Span::styled(
+            group,
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )));
+        for action in actions {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:<14}", app.keys.of(*action)), Style::default().fg(Color::Green)),
+                Span::styled(action.description(), Style::default().fg(Color::Yellow)),
+            ]));
+        }
+    }
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title("help (any key closes)")
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::Cyan)),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// input on top, matching commands below
+fn draw_palette<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let area = centered(60, 40, area);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
+        .split(area);
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled(":", Style::default().fg(Color::Yellow)),
+        Span::styled(app.palette.input.clone(), Style::default().fg(Color::Green)),
+    ]))
+    .block(
+        Block::default()
+            .title("command (Tab completes, Enter runs)")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::Cyan)),
+    );
+    let items = app
+        .palette
+        .candidates()
+        .into_iter()
+        .map(|(name, usage)| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:<10}", name), Style::default().fg(Color::Yellow)),
+                Span::styled(usage, Style::default().fg(Color::Gray)),
+            ]))
+        })
+        .collect::<Vec<_>>();
+    let mut state = ListState::default();
+    state.select(Some(app.palette.selected));
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).style(Style::default().fg(Color::Cyan)))
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+        .highlight_symbol("=>");
+    f.render_widget(Clear, area);
+    f.render_widget(input, chunks[0]);
+    f.render_stateful_widget(list, chunks[1], &mut state);
+}
+
+fn draw_popup<B: Backend>(f: &mut Frame<B>, app: &mut App, err: &WatcherErr, area: Rect) {
+    let area = centered(50, 30, area);
+    let mut text = Text::styled(err.to_string(), Style::default().fg(Color::White));
+    if let WatcherErr::Riot(WatcherError::Forbidden) = err {
+        text.extend(Text::styled(app.client.api_key.to_string(), Style::default().fg(Color::Gray)));
+    }
+    text.extend(Text::raw(""));
+    text.extend(Text::styled("press any key", Style::default().fg(Color::DarkGray)));
+    let paragraph = Paragraph::new(text)
+        .block(
+            Block::default()
+                .title(err.title())
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::Red)),
+        )
+        .wrap(Wrap { trim: true });
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// centered `percent_x` by `percent_y` part of `area`
+fn centered(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(rows[1])[1]
+}
+
+fn draw_profiles<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let area = centered(40, 40, area);
+    let items = app
+        .profiles
+        .items
+        .iter()
+        .map(|p| {
+            let region = p
+                .route
+                .map(|r| r.to_string())
+                .unwrap_or_else(|| app.route.to_string());
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("@{:<12}", p.name), Style::default().fg(Color::Yellow)),
+                Span::styled(p.riot_id.clone(), Style::default().fg(Color::Green)),
+                Span::raw(format!(" [{}]", region)),
+            ]))
+        })
+        .collect::<Vec<_>>();
+    if app.profiles.state.selected().is_none() {
+        app.profiles.state.select(Some(0));
+    }
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title("profiles")
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+        .highlight_symbol("=>");
+    f.render_widget(Clear, area);
+    f.render_stateful_widget(list, area, &mut app.profiles.state);
+}
+
 fn draw_header<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
@@ -124,11 +371,11 @@ fn draw_header<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
         .split(area);
     let text = match &app.data.summoner {
         Some(e) => e.clone().into_text(),
-        None => no_data!(),
+        None => empty(app.loads.of(Window::Header)),
     };
 
     let paragraph =
-        Paragraph::new(text).block(Block::default().borders(Borders::ALL).style(border_color(
+        Paragraph::new(text).block(Block::default().title(title(app, Window::Header)).borders(Borders::ALL).style(border_color(
             super::app::Window::Header,
             app.focus,
             None,
@@ -140,7 +387,7 @@ fn draw_header<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
             .constraints([Constraint::Percentage(20), Constraint::Percentage(80)].as_ref())
             .split(chunks[1]);
 
-        let mut text = Text::styled(app.clone().input.get(), Style::default().fg(Color::Green));
+        let mut text = Text::styled(app.input.clone().get(), Style::default().fg(Color::Green));
         if app.input.content == "" {
             text = Text::styled("Input", Style::default().fg(Color::Yellow))
         }
@@ -179,16 +426,51 @@ fn draw_conntent<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
 
     draw_rank(f, app, chunk[0]);
     draw_masteries(f, app, chunk[1]);
-    draw_games(f, app, chunks[1])
+    match app.focus {
+        Some(Window::Live) => draw_live(f, app, chunks[1]),
+        Some(Window::Summary) => draw_summary(f, app, chunks[1]),
+        _ => draw_games(f, app, chunks[1]),
+    }
+}
+
+fn draw_summary<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let text = match &app.data.summary {
+        Some(summary) => summary.into_text(),
+        None => empty(app.loads.of(Window::Summary)),
+    };
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .title(format!("summary {}", title(app, Window::Summary)))
+            .borders(Borders::ALL)
+            .style(border_color(Window::Summary, app.focus, None)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn draw_live<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let text = match &app.data.live {
+        Some(live) => live.into_text(),
+        None if app.data.live_checked && app.loads.live == Load::Idle => {
+            Text::styled("not in a game", Style::default().fg(Color::Yellow))
+        }
+        None => empty(app.loads.of(Window::Live)),
+    };
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .title(format!("live {}", title(app, Window::Live)))
+            .borders(Borders::ALL)
+            .style(border_color(Window::Live, app.focus, None)),
+    );
+    f.render_widget(paragraph, area);
 }
 
 fn draw_rank<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
     let texts: Vec<Text> = match &app.data.rank {
         Some(e) => e.iter().map(|f| f.into_text()).collect::<Vec<Text>>(),
-        None => vec![no_data!()],
+        None => vec![empty(app.loads.of(Window::Rank))],
     };
     let paragraph = Paragraph::new(concat_text(texts)).block(
-        Block::default().borders(Borders::ALL).style(border_color(
+        Block::default().title(title(app, Window::Rank)).borders(Borders::ALL).style(border_color(
             super::app::Window::Rank,
             app.focus,
             None,
@@ -200,10 +482,10 @@ fn draw_rank<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
 fn draw_masteries<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
     let texts = match &app.data.masteries {
         Some(e) => e.iter().map(|f| f.into_text()).collect::<Vec<_>>(),
-        None => vec![no_data!()],
+        None => vec![empty(app.loads.of(Window::Masteries))],
     };
     let paragraph = Paragraph::new(concat_text(texts)).block(
-        Block::default().borders(Borders::ALL).style(border_color(
+        Block::default().title(title(app, Window::Masteries)).borders(Borders::ALL).style(border_color(
             super::app::Window::Masteries,
             app.focus,
             None,
@@ -218,53 +500,38 @@ fn draw_games<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
         .constraints([Constraint::Percentage(20), Constraint::Percentage(80)].as_ref())
         .split(area);
                                                                                                                                                                     
-    #[allow(unused_assignments)]
-    let mut name: String = String::default();
-    match &app.data.current_search {
-        Some(i) => name = i.1.clone(),
-        None => name = "".to_string().clone(),
+    let puuid = match &app.data.current_search {
+        Some(player) => player.summoner.puuid.clone(),
+        None => "".to_string(),
     };
 
     let mut items: Vec<ListItem> = vec![];
     let mut state = ListState::default();
     let mut curr_game: Text = Text::from("");
+    let mut curr_id = String::new();
     let selected: MatchDisplay;
 
     match app.data.games.clone() {
         Games::G(g) => {
             state = g.state;
 
-            for (_, game) in g.items.clone().into_iter().enumerate() {
-                let text: Text;
-                let mut id: Vec<&Participant> = game
-                    .0
-                    .info
-                    .participants
-                    .iter()
-                    .filter(|f| f.summoner_name == name)
-                    .collect();
-
-                if id.len() != 0 {
-                    match id.pop().unwrap().win {
-                        true => text = Text::styled("win", Style::default().fg(Color::Green)),
-                        false => text = Text::styled("lose", Style::default().fg(Color::Red)),
-                    }
-                } else {
-                     text = Text::styled("no_data", Style::default().fg(Color::Red))
-                }
-
-                items.push(ListItem::new(text));
+            for game in &g.items {
+                items.push(ListItem::new(game.list(&puuid)));
             }
             if g.items.len() != 0 {
                 selected = g.items.index(state.selected().unwrap_or(0)).clone();
-                curr_game = selected.into_text();
+                curr_id = selected.0.metadata.match_id.clone();
+                curr_game = match app.full_scoreboard {
+                    true => ScoreboardDisplay::with(selected.0).into_text(),
+                    false => selected.into_text(),
+                };
             }
         }
-        Games::N => items.append(&mut vec![ListItem::new(Text::from("no data"))]),
+        Games::N => items.append(&mut vec![ListItem::new(empty(app.loads.of(Window::List)))]),
     };
 
     let list = List::new(items)
-        .block(Block::default().borders(Borders::ALL))
+        .block(Block::default().title(title(app, Window::List)).borders(Borders::ALL))
         .style(Style::default().fg(ratatui::style::Color::Gray))
         .highlight_style(
             Style::default()
@@ -274,8 +541,13 @@ fn draw_games<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
         .highlight_symbol("=>");
     f.render_stateful_widget(list, chunks[0], &mut state);
 
+    if let Some(timeline) = app.data.timeline.clone().filter(|t| t.match_id == curr_id) {
+        draw_timeline(f, app, chunks[1], &timeline);
+        return;
+    }
+
     let paragraph = Paragraph::new(curr_game).block(
-        Block::default().borders(Borders::ALL).style(border_color(
+        Block::default().title(title(app, Window::Games)).borders(Borders::ALL).style(border_color(
             super::app::Window::Games,
             app.focus,
             None,
@@ -284,6 +556,99 @@ fn draw_games<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
     f.render_widget(paragraph, chunks[1]);
 }
 
+/// blue minus red gold and xp, objectives and kills below
+fn draw_timeline<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect, timeline: &GameTimeline) {
+    let block = Block::default()
+        .title(format!("timeline {}", timeline.match_id))
+        .borders(Borders::ALL)
+        .style(border_color(Window::Games, app.focus, None));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)].as_ref())
+        .split(inner);
+
+    let end = timeline.gold.last().map(|p| p.0).unwrap_or(1.0).max(1.0);
+    let max = timeline
+        .gold
+        .iter()
+        .chain(&timeline.xp)
+        .map(|p| p.1.abs())
+        .fold(1000.0, f64::max);
+    let zero = [(0.0, 0.0), (end, 0.0)];
+    let datasets = vec![
+        Dataset::default()
+            .name("gold")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Yellow))
+            .data(&timeline.gold),
+        Dataset::default()
+            .name("xp")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Cyan))
+            .data(&timeline.xp),
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::DarkGray))
+            .data(&zero),
+    ];
+    let chart = Chart::new(datasets)
+        .x_axis(
+            Axis::default()
+                .title("min")
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, end])
+                .labels(vec![Span::raw("0"), Span::raw(format!("{:.0}", end / 2.0)), Span::raw(format!("{:.0}", end))]),
+        )
+        .y_axis(
+            Axis::default()
+                .title("blue - red")
+                .style(Style::default().fg(Color::Gray))
+                .bounds([-max, max])
+                .labels(vec![
+                    Span::styled(format!("-{:.1}k", max / 1000.0), Style::default().fg(Color::Red)),
+                    Span::raw("0"),
+                    Span::styled(format!("+{:.1}k", max / 1000.0), Style::default().fg(Color::Blue)),
+                ]),
+        );
+    f.render_widget(chart, chunks[0]);
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+        .split(chunks[1]);
+    let (kills, objectives): (Vec<_>, Vec<_>) = timeline
+        .events
+        .iter()
+        .cloned()
+        .partition(|e| e.kind == EventKind::Kill);
+    let objectives = Paragraph::new(TimelineEventsDisplay::with(objectives).into_text())
+        .block(Block::default().title("objectives").borders(Borders::TOP));
+    let kills = Paragraph::new(TimelineEventsDisplay::with(kills).into_text())
+        .block(Block::default().title("kills").borders(Borders::TOP));
+    f.render_widget(objectives, chunks[0]);
+    f.render_widget(kills, chunks[1]);
+}
+
+/// spinner while the panel loads
+fn title(app: &App, window: Window) -> String {
+    app.loads.of(window).title(app.tick)
+}
+
+/// what a panel without data shows, the error if its fetch failed
+fn empty(load: &Load) -> Text<'static> {
+    match load {
+        Load::Loading => Text::styled("loading", Style::default().fg(Color::Yellow)),
+        Load::Failed(e) => Text::styled(e.clone(), Style::default().fg(Color::Red)),
+        Load::Idle => no_data!(),
+    }
+}
+
 fn draw_footer<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
     let text = format!("{}", app.keys);
 
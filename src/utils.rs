@@ -1,10 +1,12 @@
 use std::fmt::Display;
-use chrono;
 
+use chrono::{NaiveDate, TimeZone};
 use crossterm::style::Stylize;
-use riven::consts::PlatformRoute;
+use riven::consts::{PlatformRoute, Queue};
 
-pub const ROUTE_NAMES: [&str; 15]= ["kr", "ru", "br", "jp", "la1", "la2", "na", "oce", "ph", "sg", "th", "tr", "tw", "eune", "euw"];
+use crate::static_data::queue_description;
+
+pub const ROUTE_NAMES: [&str; 16]= ["kr", "ru", "br", "jp", "la1", "la2", "na", "oce", "ph", "sg", "th", "tr", "tw", "vn", "eune", "euw"];
 
 #[macro_export]
 macro_rules! err_print  {
@@ -14,30 +16,6 @@ macro_rules! err_print  {
 }
 
 
-pub fn  print_help() {
-    const TEXT: &str = r###"
-            Watcher 
-______________________________
-
-Usage: Watcher [name] [region] [flags]
-
-
-# Regions:
-["kr", "ru", "br", "jp", "la1", "la2", "na", "oce", "ph", "sg", "th", "tr", "tw", "eune", "euw"]
-Flags:
-------------------------------
-NONE     lunches Tui 
--h | --help     prints help
--s | --summoner searches for summoner
--r | --rank     get's summoner rank 
--m | --mastery  get's first 10 highest champions mastery's
--g | --game     -g 0..20 get's game from 20 games
-        "###;
-
-    println!("{}", TEXT);
-}
-
-
 pub fn routes() -> Vec<(String, PlatformRoute)> {
     let mut map: Vec<(String, PlatformRoute)> = vec![];
         map.push(("kr".to_string(), PlatformRoute::KR));
@@ -53,6 +31,7 @@ pub fn routes() -> Vec<(String, PlatformRoute)> {
         map.push(("th".to_string(), PlatformRoute::TH2));
         map.push(("tr".to_string(), PlatformRoute::TR1));
         map.push(("tw".to_string(), PlatformRoute::TW2));
+        map.push(("vn".to_string(), PlatformRoute::VN2));
         map.push(("eune".to_string(), PlatformRoute::EUN1));
         map.push(("euw".to_string(), PlatformRoute::EUW1));
         map
@@ -70,14 +49,45 @@ pub  fn parse_route(key: String) -> PlatformRoute {
 
 }
 
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
+/// like parse_route but None for anything not in ROUTE_NAMES
+pub fn route_by_name(name: &str) -> Option<PlatformRoute> {
+    let name = name.to_lowercase();
+    routes()
+        .into_iter()
+        .find(|f| f.0 == name)
+        .map(|f| f.1)
+}
+
+/// short name of `route` as in ROUTE_NAMES
+pub fn route_name(route: PlatformRoute) -> Option<String> {
+    routes()
+        .into_iter()
+        .find(|f| f.1 == route)
+        .map(|f| f.0)
+}
+
+/// ordered by severity, the log view shows one level and above
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum  LogKind {
+    /// api timings
+    Debug,
     Info,
     Warning,
     Error,
 }
 
+impl LogKind {
+    /// next level filter of the log view
+    pub fn next(&self) -> LogKind {
+        match self {
+            LogKind::Debug => LogKind::Info,
+            LogKind::Info => LogKind::Warning,
+            LogKind::Warning => LogKind::Error,
+            LogKind::Error => LogKind::Debug,
+        }
+    }
+}
+
 
 #[derive(Debug, Clone)]
 pub struct Log {
@@ -89,7 +99,7 @@ pub msg: String
 impl  Log {
     pub fn new(kind: LogKind, msg: String) -> Log {                                                                                                                                             
         let now = chrono::offset::Local::now(); 
-        let time_stamp = now.date_naive().to_string();
+        let time_stamp = now.format("%Y-%m-%d %H:%M:%S%.3f").to_string();
         Log { kind, time_stamp, msg}
     }
 }
@@ -98,6 +108,7 @@ impl  Display for Log {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let log = self.clone();
         let msg = match log.kind {
+                    LogKind::Debug => log.msg.with(crossterm::style::Color::DarkGrey),
                     LogKind::Info => log.msg.with(crossterm::style::Color::Green),
                     LogKind::Warning => log.msg.with(crossterm::style::Color::Yellow),
                     LogKind::Error =>  log.msg.with(crossterm::style::Color::Red)
@@ -109,9 +120,62 @@ impl  Display for Log {
     }
 }
 
-pub fn is_numeric(input: &str) -> bool {
-    match input.parse::<usize>(){
-        Ok(_) => true,
-        Err(..) => false,
+pub const QUEUE_NAMES: [(&str, u16); 6] = [
+    ("solo", 420),
+    ("flex", 440),
+    ("draft", 400),
+    ("blind", 430),
+    ("aram", 450),
+    ("clash", 700),
+];
+
+/// queue id or one of QUEUE_NAMES
+pub fn parse_queue(input: &str) -> Option<Queue> {
+    if let Ok(id) = input.parse::<u16>() {
+        return Some(Queue::from(id));
+    }
+    QUEUE_NAMES
+        .iter()
+        .find(|(name, _)| *name == input.to_lowercase())
+        .map(|(_, id)| Queue::from(*id))
+}
+
+/// one of QUEUE_NAMES, the imported description or the raw queue id
+pub fn queue_name(id: u16) -> String {
+    QUEUE_NAMES
+        .iter()
+        .find(|(_, queue)| *queue == id)
+        .map(|(name, _)| name.to_string())
+        .or_else(|| queue_description(id as i64))
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// unix millis as local `YYYY-MM-DD HH:MM`
+pub fn format_date(millis: i64) -> String {
+    chrono::offset::Local
+        .timestamp_millis_opt(millis)
+        .single()
+        .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+/// unix seconds or YYYY-MM-DD (local midnight)
+pub fn parse_time(input: &str) -> Option<i64> {
+    if let Ok(secs) = input.parse::<i64>() {
+        return Some(secs);
+    }
+    let date = NaiveDate::parse_from_str(input, "%Y-%m-%d").ok()?;
+    chrono::offset::Local
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+        .earliest()
+        .map(|t| t.timestamp())
+}
+
+/// game length in seconds, matches from before patch 11.20 report milliseconds
+/// and have no `game_end_timestamp`
+pub fn duration_secs(info: &riven::models::match_v5::Info) -> i64 {
+    match info.game_end_timestamp {
+        Some(_) => info.game_duration,
+        None => info.game_duration / 1000,
     }
 }